@@ -1,15 +1,205 @@
-use std::fs::File;
-use std::io::Read;
-use gtnh_recipe_lib::types::gregtech_recipe::{filter_recipes_by_input_fluid, filter_recipes_by_output_fluid, Recipes};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use gtnh_recipe_lib::export::flow::{from_flow_yaml, to_flow_yaml, FlowRecipe};
+use gtnh_recipe_lib::export::graph::RecipeGraph;
+use gtnh_recipe_lib::export::sqlite::export_sqlite;
+use gtnh_recipe_lib::export::table::{recipes_to_table, TableFormat};
+use gtnh_recipe_lib::load::to_binary;
+use gtnh_recipe_lib::types::gregtech_recipe::{filter_recipes_by_input_fluid, filter_recipes_by_output_fluid, GregtechRecipe, Recipes};
 use gtnh_recipe_lib::types::json::JsonFormat;
 use gtnh_recipe_lib::types::recipe_balance::RecipeBalance;
 use gtnh_recipe_lib::types::recipe_stats::RecipeStats;
 
+#[derive(Parser)]
+#[command(version, about = "Command line tools for GTNH recipe dumps")]
+struct Cli {
+    /// Recipe dump exported by RecEx, or a binary cache written by `cache`
+    #[arg(short, long, default_value = "./recipes_2.json")]
+    file: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print recipe counts per machine and an example nitric acid chain (default)
+    Stats,
+    /// Export recipes as an item/recipe graph
+    Graph {
+        #[command(flatten)]
+        selection: RecipeSelection,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+        /// Output file, prints to stdout if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Export recipes as a gtnh-flow YAML project
+    FlowExport {
+        #[command(flatten)]
+        selection: RecipeSelection,
+        /// Output rate target as NAME=RATE, set on every recipe producing NAME
+        #[arg(long, value_parser = parse_target)]
+        target: Vec<(String, f64)>,
+        /// Output file, prints to stdout if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Export recipes as a CSV or TSV table
+    TableExport {
+        #[command(flatten)]
+        selection: RecipeSelection,
+        #[arg(long, value_enum, default_value_t = TableFormatArg::Csv)]
+        format: TableFormatArg,
+        /// Output file, prints to stdout if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write all machine recipes into a SQLite database
+    SqliteExport {
+        /// Database file, replaced if it exists
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Write a binary cache of the dump, which loads much faster than the JSON
+    Cache {
+        /// Cache file, e.g. recipes.bin
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Read a gtnh-flow YAML project and print its recipes
+    FlowImport {
+        /// gtnh-flow project file
+        project: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct RecipeSelection {
+    /// Fuzzy search keyword matched against ingredient names
+    #[arg(short, long)]
+    search: Option<String>,
+    /// Only use recipes of this machine
+    #[arg(short, long)]
+    machine: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TableFormatArg {
+    Csv,
+    Tsv,
+}
+
+impl From<TableFormatArg> for TableFormat {
+    fn from(format: TableFormatArg) -> Self {
+        match format {
+            TableFormatArg::Csv => TableFormat::Csv,
+            TableFormatArg::Tsv => TableFormat::Tsv,
+        }
+    }
+}
+
 fn main() {
-    let mut file = File::open("./recipes_2.json").unwrap();
-    let mut file_str = String::new();
-    file.read_to_string(&mut file_str).expect("unable to read file to string");
-    let deserialized: JsonFormat = serde_json::from_str(&file_str).unwrap();
+    let cli = Cli::parse();
+    let deserialized = gtnh_recipe_lib::load_file(&cli.file);
+    match cli.command.unwrap_or(Command::Stats) {
+        Command::Stats => print_stats(&deserialized),
+        Command::Graph { selection, format, output } => {
+            let graph = RecipeGraph::from_recipes(&select_recipes(&deserialized, &selection));
+            let text = match format {
+                GraphFormat::Dot => graph.to_dot(),
+                GraphFormat::Mermaid => graph.to_mermaid(),
+            };
+            write_output(output, &text);
+        }
+        Command::FlowExport { selection, target, output } => {
+            let flow_recipes = select_recipes(&deserialized, &selection)
+                .iter()
+                .map(|(machine_name, recipe)| {
+                    let mut flow_recipe = FlowRecipe::from_recipe(machine_name, recipe);
+                    // Keyed by the output's spelling, so gtnh-flow matches the target to it
+                    let targets = target
+                        .iter()
+                        .filter_map(|(name, rate)| {
+                            let output = flow_recipe.outputs.keys().find(|output| output.eq_ignore_ascii_case(name))?;
+                            Some((output.clone(), *rate))
+                        })
+                        .collect::<BTreeMap<String, f64>>();
+                    if !targets.is_empty() {
+                        flow_recipe.target = Some(targets);
+                    }
+                    flow_recipe
+                })
+                .collect::<Vec<FlowRecipe>>();
+            write_output(output, &to_flow_yaml(&flow_recipes).expect("unable to serialize project"));
+        }
+        Command::TableExport { selection, format, output } => {
+            let table = recipes_to_table(&select_recipes(&deserialized, &selection), format.into())
+                .expect("unable to serialize table");
+            write_output(output, &table);
+        }
+        Command::SqliteExport { output } => {
+            if output.exists() {
+                fs::remove_file(&output).expect("unable to replace existing database");
+            }
+            export_sqlite(&deserialized, &output).expect("unable to write database");
+        }
+        Command::Cache { output } => {
+            let bytes = to_binary(&deserialized).expect("unable to encode cache");
+            fs::write(&output, bytes).expect("unable to write cache file");
+        }
+        Command::FlowImport { project } => {
+            let yaml = fs::read_to_string(project).expect("unable to read project file");
+            let fluid_names = deserialized.fluid_names();
+            for flow_recipe in from_flow_yaml(&yaml).expect("unable to parse project file") {
+                let (machine_name, recipe) = flow_recipe.to_recipe(&fluid_names);
+                println!("{} ({}, {} EU/t, {} ticks): {}", machine_name, flow_recipe.tier, recipe.eut, recipe.duration, recipe);
+            }
+        }
+    }
+}
+
+fn parse_target(target: &str) -> Result<(String, f64), String> {
+    let (name, rate) = target
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected NAME=RATE, got '{}'", target))?;
+    let rate = rate.parse::<f64>().map_err(|err| err.to_string())?;
+    Ok((name.to_string(), rate))
+}
+
+// `Option::is_none_or` needs Rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+fn select_recipes(recipes: &JsonFormat, selection: &RecipeSelection) -> Vec<(String, GregtechRecipe)> {
+    match (&selection.search, &selection.machine) {
+        (Some(keyword), machine) => recipes
+            .search(keyword)
+            .into_iter()
+            .filter(|(machine_name, _)| machine.as_ref().map_or(true, |machine| machine == machine_name))
+            .collect(),
+        (None, Some(machine)) => recipes.recipes_of_machine(machine),
+        (None, None) => {
+            eprintln!("Select recipes with --search and/or --machine");
+            std::process::exit(2);
+        }
+    }
+}
+
+fn write_output(output: Option<PathBuf>, text: &str) {
+    match output {
+        Some(path) => fs::write(&path, text).expect("unable to write output file"),
+        None => print!("{}", text),
+    }
+}
+
+fn print_stats(deserialized: &JsonFormat) {
     //dbg!(&deserialized);
     let mut source_iter = deserialized.sources.iter();
     let gregtech_source = source_iter.next().unwrap();
@@ -22,7 +212,7 @@ fn main() {
         let mut max_item_output_count = 0;
         let mut max_fluid_input_count = 0;
         let mut max_fluid_output_count = 0;
-        for recipe in machine.clone().recipes.clone() {
+        for recipe in &machine.recipes {
             if recipe.item_inputs.len() > max_item_input_count {max_item_input_count = recipe.item_inputs.len()};
             if recipe.item_outputs.len() > max_item_output_count {max_item_output_count = recipe.item_outputs.len()};
             if recipe.fluid_inputs.len() > max_fluid_input_count {max_fluid_input_count = recipe.fluid_inputs.len()};
@@ -44,13 +234,13 @@ fn main() {
 
     let large_chemical_reactor_recipes = &gregtech_source.machines.iter().find(|&machine| machine.name=="Large Chemical Reactor").unwrap().recipes;
     //dbg!(&large_chemical_reactor_recipes);
-    let nitric_acid_output_recipes = filter_recipes_by_output_fluid(&large_chemical_reactor_recipes, "Nitric Acid", Some(2000));
+    let nitric_acid_output_recipes = filter_recipes_by_output_fluid(large_chemical_reactor_recipes, "Nitric Acid", Some(2000));
     dbg!(nitric_acid_output_recipes.len());
     println!("Found these Recipes on 'Large Chemical Reactor' with output fluid 'Nitric Acid (2000l)': \n{}", Recipes::from(nitric_acid_output_recipes.clone()));
     let no2_to_nitric_acid_recipes = filter_recipes_by_input_fluid(&nitric_acid_output_recipes, "Nitrogen Dioxide", Some(3000));
     println!("Filtered by input fluid 'Nitrogen Dioxide (3000l)': \n{}", Recipes::from(no2_to_nitric_acid_recipes.clone()));
 
-    let no_to_no2_recipe = filter_recipes_by_input_fluid(&filter_recipes_by_output_fluid(&large_chemical_reactor_recipes, "Nitrogen Dioxide", Some(1000)), "Nitric Oxide", Some(1000));
+    let no_to_no2_recipe = filter_recipes_by_input_fluid(&filter_recipes_by_output_fluid(large_chemical_reactor_recipes, "Nitrogen Dioxide", Some(1000)), "Nitric Oxide", Some(1000));
     println!("NO to NO2: \n{}", Recipes::from(no_to_no2_recipe.clone()));
 
     let balance = RecipeBalance::new(no2_to_nitric_acid_recipes.first().unwrap(), no_to_no2_recipe.first().unwrap());
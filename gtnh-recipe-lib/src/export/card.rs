@@ -0,0 +1,217 @@
+use std::fmt::Write;
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::production_chain::{ChainSolution, IngredientRate};
+
+/// Font size of the card body in SVG pixels, the title is drawn 1.25 times larger
+const SVG_FONT_SIZE: f32 = 14.0;
+/// Width of a character of the monospace SVG font relative to its size
+const SVG_CHAR_WIDTH: f32 = 0.6;
+const SVG_PADDING: f32 = 12.0;
+const SVG_CARD_GAP: f32 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardFormat {
+    Markdown,
+    Text,
+    Svg,
+}
+
+impl CardFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CardFormat::Markdown => "md",
+            CardFormat::Text => "txt",
+            CardFormat::Svg => "svg",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardLineStyle {
+    Title,
+    Subtitle,
+    Heading,
+    /// Entry of a section, drawn indented
+    Entry,
+}
+
+/// Recipe or production chain summary for sharing, e.g. in chat
+#[derive(Clone, Debug, PartialEq)]
+pub struct Card {
+    pub title: String,
+    pub subtitle: String,
+    /// Headings with their lines, empty sections are left out
+    pub sections: Vec<(String, Vec<String>)>,
+}
+
+impl Card {
+    pub fn from_recipe(machine_name: &str, recipe: &GregtechRecipe) -> Self {
+        let inputs = recipe.item_inputs.iter().map(|item| item.to_string())
+            .chain(recipe.fluid_inputs.iter().map(|fluid| fluid.to_string()))
+            .collect();
+        let outputs = recipe.item_outputs.iter().map(|item| item.to_string())
+            .chain(recipe.fluid_outputs.iter().map(|fluid| fluid.to_string()))
+            .collect();
+        Card {
+            title: machine_name.to_string(),
+            subtitle: format!("{} EU/t, {}s", recipe.eut, recipe.duration as f64 / 20.0),
+            sections: vec![("Inputs".to_string(), inputs), ("Outputs".to_string(), outputs)],
+        }
+    }
+
+    /// Card of a solved production chain making `rate` of `target` per second.
+    pub fn from_solution(solution: &ChainSolution, target: &str, rate: f64) -> Self {
+        let machines = solution.steps
+            .iter()
+            .map(|step| format!("{:.2} × {} ({})", step.machines, step.machine_name, step.tier))
+            .collect();
+        let rates = |rates: &[IngredientRate]| {
+            rates.iter().map(|rate| format!("{:.2}/s {}", rate.rate, rate.name)).collect()
+        };
+        Card {
+            title: format!("{:.2}/s {}", rate, target),
+            subtitle: format!("{:.1} EU/t", solution.power),
+            sections: vec![
+                ("Machines".to_string(), machines),
+                ("Inputs".to_string(), rates(&solution.inputs)),
+                ("Byproducts".to_string(), rates(&solution.byproducts(target))),
+            ],
+        }
+    }
+
+    fn sections(&self) -> impl Iterator<Item = &(String, Vec<String>)> {
+        self.sections.iter().filter(|(_, lines)| !lines.is_empty())
+    }
+
+    /// Text lines of the card from top to bottom, for rendering it as an image.
+    pub fn lines(&self) -> Vec<(CardLineStyle, String)> {
+        let mut lines = vec![(CardLineStyle::Title, self.title.clone()), (CardLineStyle::Subtitle, self.subtitle.clone())];
+        for (heading, entries) in self.sections() {
+            lines.push((CardLineStyle::Heading, format!("{}:", heading)));
+            lines.extend(entries.iter().map(|entry| (CardLineStyle::Entry, entry.clone())));
+        }
+        lines
+    }
+}
+
+pub fn cards_to_string(cards: &[Card], format: CardFormat) -> String {
+    match format {
+        CardFormat::Markdown => cards_to_markdown(cards),
+        CardFormat::Text => cards_to_text(cards),
+        CardFormat::Svg => cards_to_svg(cards),
+    }
+}
+
+fn cards_to_markdown(cards: &[Card]) -> String {
+    let mut markdown = String::new();
+    for card in cards {
+        let _ = writeln!(markdown, "**{}** ({})", card.title, card.subtitle);
+        for (heading, lines) in card.sections() {
+            let _ = writeln!(markdown, "- {}:", heading);
+            for line in lines {
+                let _ = writeln!(markdown, "  - {}", line);
+            }
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
+
+fn cards_to_text(cards: &[Card]) -> String {
+    let mut text = String::new();
+    for card in cards {
+        let _ = writeln!(text, "{} ({})", card.title, card.subtitle);
+        for (heading, lines) in card.sections() {
+            let _ = writeln!(text, "  {}: {}", heading, lines.join(" + "));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Cards stacked vertically, drawn with a monospace font so the size can be computed up front.
+fn cards_to_svg(cards: &[Card]) -> String {
+    let line_height = SVG_FONT_SIZE * 1.4;
+    let longest_line = cards
+        .iter()
+        .flat_map(|card| card.lines())
+        .map(|(style, line)| match style {
+            // The title is larger, entries are indented by two characters
+            CardLineStyle::Title => line.chars().count() + line.chars().count().div_ceil(4),
+            CardLineStyle::Entry => line.chars().count() + 2,
+            CardLineStyle::Subtitle | CardLineStyle::Heading => line.chars().count(),
+        })
+        .max()
+        .unwrap_or(0);
+    let width = (longest_line as f32 * SVG_FONT_SIZE * SVG_CHAR_WIDTH + 2.0 * SVG_PADDING).ceil();
+    let card_height = |card: &Card| card.lines().len() as f32 * line_height + 2.0 * SVG_PADDING;
+    let height = cards.iter().map(|card| card_height(card) + SVG_CARD_GAP).sum::<f32>().max(SVG_CARD_GAP) - SVG_CARD_GAP;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="{}">"#,
+        width, height.ceil(), SVG_FONT_SIZE
+    );
+    let mut top = 0.0;
+    for card in cards {
+        let _ = writeln!(
+            svg,
+            r##"<rect x="0" y="{}" width="{}" height="{}" rx="6" fill="#1b1b1b" stroke="#5a5a5a"/>"##,
+            top, width, card_height(card)
+        );
+        let mut baseline = top + SVG_PADDING + line_height * 0.8;
+        for (style, line) in card.lines() {
+            let (x, attributes) = match style {
+                CardLineStyle::Title => (SVG_PADDING, format!(r##"fill="#ffffff" font-size="{}" font-weight="bold""##, SVG_FONT_SIZE * 1.25)),
+                CardLineStyle::Subtitle => (SVG_PADDING, r##"fill="#9ccfff""##.to_string()),
+                CardLineStyle::Heading => (SVG_PADDING, r##"fill="#ffd479""##.to_string()),
+                CardLineStyle::Entry => (SVG_PADDING + 2.0 * SVG_FONT_SIZE * SVG_CHAR_WIDTH, r##"fill="#dddddd""##.to_string()),
+            };
+            let _ = writeln!(svg, r#"<text x="{}" y="{}" {}>{}</text>"#, x, baseline, attributes, escape_xml(&line));
+            baseline += line_height;
+        }
+        top += card_height(card) + SVG_CARD_GAP;
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::RecipeBuilder;
+
+    fn card() -> Card {
+        let recipe = RecipeBuilder::new(30, 200).item_inputs(&[("Bronze <Ring>", 4)]).fluid_outputs(&[("Steam", 1000)]).build();
+        Card::from_recipe("Assembler", &recipe)
+    }
+
+    #[test]
+    fn test_text_formats() {
+        let cards = [card()];
+        assert_eq!(
+            cards_to_string(&cards, CardFormat::Markdown),
+            "**Assembler** (30 EU/t, 10s)\n- Inputs:\n  - 4x Bronze <Ring>\n- Outputs:\n  - 1000l Steam\n\n"
+        );
+        assert_eq!(
+            cards_to_string(&cards, CardFormat::Text),
+            "Assembler (30 EU/t, 10s)\n  Inputs: 4x Bronze <Ring>\n  Outputs: 1000l Steam\n\n"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = cards_to_string(&[card(), card()], CardFormat::Svg);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("4x Bronze &lt;Ring&gt;"));
+        assert_eq!(svg.matches("<rect ").count(), 2);
+        // Title, subtitle, two headings and two ingredients per card
+        assert_eq!(svg.matches("<text ").count(), 12);
+        assert_eq!(card().lines().len(), 6);
+    }
+}
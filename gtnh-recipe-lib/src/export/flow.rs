@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::ingredients::fluid::Fluid;
+use crate::types::ingredients::item::Item;
+use crate::types::voltage_tier::VoltageTier;
+
+/// One entry of a gtnh-flow project file.
+///
+/// gtnh-flow does not distinguish items and fluids and expects the duration in seconds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlowRecipe {
+    #[serde(rename = "m")]
+    pub machine: String,
+    pub tier: String,
+    #[serde(rename = "I", default)]
+    pub inputs: BTreeMap<String, f64>,
+    #[serde(rename = "O", default)]
+    pub outputs: BTreeMap<String, f64>,
+    pub eut: usize,
+    /// Duration in seconds
+    pub dur: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<f64>,
+    /// Requested output rates per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<BTreeMap<String, f64>>,
+}
+
+impl FlowRecipe {
+    pub fn from_recipe(machine_name: &str, recipe: &GregtechRecipe) -> Self {
+        let mut inputs = BTreeMap::new();
+        for item in &recipe.item_inputs {
+            *inputs.entry(item.get_name()).or_insert(0.0) += item.amount as f64;
+        }
+        for fluid in &recipe.fluid_inputs {
+            *inputs.entry(fluid.get_name()).or_insert(0.0) += fluid.amount as f64;
+        }
+        let mut outputs = BTreeMap::new();
+        for item in &recipe.item_outputs {
+            *outputs.entry(item.get_name()).or_insert(0.0) += item.amount as f64;
+        }
+        for fluid in &recipe.fluid_outputs {
+            *outputs.entry(fluid.get_name()).or_insert(0.0) += fluid.amount as f64;
+        }
+
+        FlowRecipe {
+            machine: machine_name.to_string(),
+            tier: VoltageTier::for_eut(recipe.eut).to_string(),
+            inputs,
+            outputs,
+            eut: recipe.eut,
+            dur: recipe.duration as f64 / 20.0,
+            group: None,
+            number: None,
+            target: None,
+        }
+    }
+
+    /// Converts the entry back to a recipe, using `fluid_names` to decide whether an ingredient is a fluid.
+    ///
+    /// Names are compared ignoring case, as gtnh-flow projects usually use lowercase names.
+    pub fn to_recipe(&self, fluid_names: &HashSet<String>) -> (String, GregtechRecipe) {
+        let fluid_names = fluid_names.iter().map(|name| name.to_lowercase()).collect::<HashSet<String>>();
+        let mut recipe = GregtechRecipe {
+            enabled: true,
+            duration: (self.dur * 20.0).round() as usize,
+            eut: self.eut,
+            item_inputs: vec![],
+            item_outputs: vec![],
+            fluid_inputs: vec![],
+            fluid_outputs: vec![],
+        };
+        for (name, amount) in &self.inputs {
+            if fluid_names.contains(&name.to_lowercase()) {
+                recipe.fluid_inputs.push(flow_fluid(name, *amount));
+            } else {
+                recipe.item_inputs.push(flow_item(name, *amount));
+            }
+        }
+        for (name, amount) in &self.outputs {
+            if fluid_names.contains(&name.to_lowercase()) {
+                recipe.fluid_outputs.push(flow_fluid(name, *amount));
+            } else {
+                recipe.item_outputs.push(flow_item(name, *amount));
+            }
+        }
+        (self.machine.clone(), recipe)
+    }
+}
+
+fn flow_item(name: &str, amount: f64) -> Item {
+    Item {
+        amount: amount.round() as usize,
+        unlocalized_name: None,
+        localized_name: Some(name.to_string()),
+    }
+}
+
+fn flow_fluid(name: &str, amount: f64) -> Fluid {
+    Fluid {
+        amount: amount.round() as usize,
+        unlocalized_name: None,
+        localized_name: Some(name.to_string()),
+    }
+}
+
+pub fn to_flow_yaml(recipes: &[FlowRecipe]) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(recipes)
+}
+
+pub fn from_flow_yaml(yaml: &str) -> Result<Vec<FlowRecipe>, serde_yaml::Error> {
+    serde_yaml::from_str(yaml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NITRIC_ACID_PROJECT: &str = "
+- m: large chemical reactor
+  tier: HV
+  I:
+    nitrogen dioxide: 3000
+    oxygen gas: 1000
+    water: 1000
+  O:
+    nitric acid: 4000
+  eut: 30
+  dur: 12
+  target:
+    nitric acid: 1000
+";
+
+    #[test]
+    fn test_import() {
+        let project = from_flow_yaml(NITRIC_ACID_PROJECT).unwrap();
+        assert_eq!(project.len(), 1);
+        assert_eq!(project[0].machine, "large chemical reactor");
+        assert_eq!(project[0].target.as_ref().unwrap()["nitric acid"], 1000.0);
+
+        let fluid_names = HashSet::from([
+            "nitrogen dioxide".to_string(),
+            "oxygen gas".to_string(),
+            "water".to_string(),
+            "nitric acid".to_string(),
+        ]);
+        let (machine_name, recipe) = project[0].to_recipe(&fluid_names);
+        assert_eq!(machine_name, "large chemical reactor");
+        assert_eq!(recipe.duration, 240);
+        assert_eq!(recipe.fluid_inputs.len(), 3);
+        assert!(recipe.item_inputs.is_empty());
+        assert_eq!(recipe.fluid_outputs[0].amount, 4000);
+
+        // The dump spells fluids like "Water" and lacks the nitrogen oxides
+        let recipes = crate::load_file(&std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../recipes_small.json")));
+        let (_, recipe) = project[0].to_recipe(&recipes.fluid_names());
+        let names = |fluids: &[Fluid]| fluids.iter().map(|fluid| fluid.get_name()).collect::<Vec<String>>();
+        assert_eq!(names(&recipe.fluid_inputs), ["oxygen gas", "water"]);
+        assert_eq!(recipe.item_inputs.len(), 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let recipe = GregtechRecipe {
+            enabled: true,
+            duration: 30,
+            eut: 120,
+            item_inputs: vec![Item {
+                amount: 2,
+                unlocalized_name: Some("gt.metaitem.01.2032".to_string()),
+                localized_name: Some("Iron Dust".to_string()),
+            }],
+            item_outputs: vec![],
+            fluid_inputs: vec![],
+            fluid_outputs: vec![Fluid {
+                amount: 288,
+                unlocalized_name: Some("molten.iron".to_string()),
+                localized_name: Some("Molten Iron".to_string()),
+            }],
+        };
+        let flow_recipe = FlowRecipe::from_recipe("Fluid Extractor", &recipe);
+        assert_eq!(flow_recipe.tier, "MV");
+        assert_eq!(flow_recipe.dur, 1.5);
+
+        let yaml = to_flow_yaml(&[flow_recipe]).unwrap();
+        let imported = from_flow_yaml(&yaml).unwrap();
+        let (_, imported_recipe) = imported[0].to_recipe(&HashSet::from(["Molten Iron".to_string()]));
+        assert_eq!(imported_recipe.duration, 30);
+        assert_eq!(imported_recipe.item_inputs[0].get_name(), "Iron Dust");
+        assert_eq!(imported_recipe.fluid_outputs[0].amount, 288);
+    }
+}
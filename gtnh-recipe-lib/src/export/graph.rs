@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::production_chain::{ChainSolution, ProductionChain};
+pub use crate::types::ingredients::IngredientKind;
+
+/// Fill colours used for recipe nodes, assigned per machine in order of first appearance.
+const MACHINE_COLORS: [&str; 10] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3",
+    "#fdb462", "#b3de69", "#fccde5", "#d9d9d9", "#bc80bd",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IngredientNode {
+    pub kind: IngredientKind,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipeNode {
+    pub machine_name: String,
+    pub duration: usize,
+    pub eut: usize,
+    /// Crafts per second, if the graph was built from a solved chain.
+    pub rate: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeDirection {
+    /// Ingredient is consumed by the recipe
+    Input,
+    /// Ingredient is produced by the recipe
+    Output,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edge {
+    pub ingredient: usize,
+    pub recipe: usize,
+    pub direction: EdgeDirection,
+    pub amount: usize,
+}
+
+/// Bipartite graph of ingredients and the recipes consuming or producing them.
+#[derive(Clone, Debug, Default)]
+pub struct RecipeGraph {
+    pub ingredients: Vec<IngredientNode>,
+    pub recipes: Vec<RecipeNode>,
+    pub edges: Vec<Edge>,
+    ingredient_indices: HashMap<(String, IngredientKind), usize>,
+}
+
+impl RecipeGraph {
+    /// Builds a graph labelling edges with the amounts of a single craft.
+    pub fn from_recipes(recipes: &[(String, GregtechRecipe)]) -> Self {
+        let mut graph = RecipeGraph::default();
+        for (machine_name, recipe) in recipes {
+            graph.add_recipe(machine_name, recipe, None);
+        }
+        graph
+    }
+
+    /// Builds a graph from a solved chain, labelling edges with amounts per second.
+    pub fn from_rated_recipes(recipes: &[(String, GregtechRecipe, f64)]) -> Self {
+        let mut graph = RecipeGraph::default();
+        for (machine_name, recipe, rate) in recipes {
+            graph.add_recipe(machine_name, recipe, Some(*rate));
+        }
+        graph
+    }
+
+    /// Builds a graph of a solved production chain, skipping steps that do not run.
+    pub fn from_chain(chain: &ProductionChain, solution: &ChainSolution) -> Self {
+        let mut graph = RecipeGraph::default();
+        for (step, step_solution) in chain.steps.iter().zip(&solution.steps) {
+            if step_solution.crafts_per_second > 0.0 {
+                graph.add_recipe(&step.machine_name, &step.recipe, Some(step_solution.crafts_per_second));
+            }
+        }
+        graph
+    }
+
+    pub fn add_recipe(&mut self, machine_name: &str, recipe: &GregtechRecipe, rate: Option<f64>) {
+        let recipe_index = self.recipes.len();
+        self.recipes.push(RecipeNode {
+            machine_name: machine_name.to_string(),
+            duration: recipe.duration,
+            eut: recipe.eut,
+            rate,
+        });
+
+        for item in &recipe.item_inputs {
+            let key = item.unlocalized_name.clone().unwrap_or_else(|| item.get_name());
+            let ingredient = self.ingredient_index(key, IngredientKind::Item, item.get_name());
+            self.add_edge(ingredient, recipe_index, EdgeDirection::Input, item.amount);
+        }
+        for fluid in &recipe.fluid_inputs {
+            let key = fluid.unlocalized_name.clone().unwrap_or_else(|| fluid.get_name());
+            let ingredient = self.ingredient_index(key, IngredientKind::Fluid, fluid.get_name());
+            self.add_edge(ingredient, recipe_index, EdgeDirection::Input, fluid.amount);
+        }
+        for item in &recipe.item_outputs {
+            let key = item.unlocalized_name.clone().unwrap_or_else(|| item.get_name());
+            let ingredient = self.ingredient_index(key, IngredientKind::Item, item.get_name());
+            self.add_edge(ingredient, recipe_index, EdgeDirection::Output, item.amount);
+        }
+        for fluid in &recipe.fluid_outputs {
+            let key = fluid.unlocalized_name.clone().unwrap_or_else(|| fluid.get_name());
+            let ingredient = self.ingredient_index(key, IngredientKind::Fluid, fluid.get_name());
+            self.add_edge(ingredient, recipe_index, EdgeDirection::Output, fluid.amount);
+        }
+    }
+
+    fn ingredient_index(&mut self, key: String, kind: IngredientKind, name: String) -> usize {
+        let next_index = self.ingredients.len();
+        let index = *self
+            .ingredient_indices
+            .entry((key, kind))
+            .or_insert(next_index);
+        if index == next_index {
+            self.ingredients.push(IngredientNode { kind, name });
+        }
+        index
+    }
+
+    fn add_edge(&mut self, ingredient: usize, recipe: usize, direction: EdgeDirection, amount: usize) {
+        // Recipes may list the same ingredient more than once, merge those into one edge
+        if let Some(edge) = self.edges.iter_mut().find(|edge| {
+            edge.ingredient == ingredient && edge.recipe == recipe && edge.direction == direction
+        }) {
+            edge.amount += amount;
+        } else {
+            self.edges.push(Edge { ingredient, recipe, direction, amount });
+        }
+    }
+
+    fn edge_label(&self, edge: &Edge) -> String {
+        let unit = match self.ingredients[edge.ingredient].kind {
+            IngredientKind::Item => "x",
+            IngredientKind::Fluid => "l",
+        };
+        match self.recipes[edge.recipe].rate {
+            Some(rate) => format!("{:.2}{}/s", edge.amount as f64 * rate, unit),
+            None => format!("{}{}", edge.amount, unit),
+        }
+    }
+
+    fn recipe_label(&self, recipe: &RecipeNode) -> Vec<String> {
+        let mut lines = vec![
+            recipe.machine_name.clone(),
+            format!("{} EU/t, {} ticks", recipe.eut, recipe.duration),
+        ];
+        if let Some(rate) = recipe.rate {
+            lines.push(format!("{:.3} crafts/s", rate));
+        }
+        lines
+    }
+
+    /// Machine names in order of first appearance, and the machine index of every recipe node.
+    fn machine_classes(&self) -> (Vec<String>, Vec<usize>) {
+        let mut machines = Vec::<String>::new();
+        let classes = self
+            .recipes
+            .iter()
+            .map(|recipe| {
+                match machines.iter().position(|machine| *machine == recipe.machine_name) {
+                    Some(index) => index,
+                    None => {
+                        machines.push(recipe.machine_name.clone());
+                        machines.len() - 1
+                    }
+                }
+            })
+            .collect();
+        (machines, classes)
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let (_, classes) = self.machine_classes();
+        let mut dot = String::new();
+        writeln!(dot, "digraph recipes {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        for (index, ingredient) in self.ingredients.iter().enumerate() {
+            let style = match ingredient.kind {
+                IngredientKind::Item => "shape=ellipse",
+                IngredientKind::Fluid => "shape=ellipse, style=dashed",
+            };
+            writeln!(dot, "    i{} [label=\"{}\", {}];", index, escape_dot(&ingredient.name), style).unwrap();
+        }
+        for (index, recipe) in self.recipes.iter().enumerate() {
+            let label = self
+                .recipe_label(recipe)
+                .iter()
+                .map(|line| escape_dot(line))
+                .collect::<Vec<String>>()
+                .join("\\n");
+            writeln!(
+                dot,
+                "    r{} [label=\"{}\", shape=box, style=filled, fillcolor=\"{}\"];",
+                index,
+                label,
+                MACHINE_COLORS[classes[index] % MACHINE_COLORS.len()]
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            let (from, to) = match edge.direction {
+                EdgeDirection::Input => (format!("i{}", edge.ingredient), format!("r{}", edge.recipe)),
+                EdgeDirection::Output => (format!("r{}", edge.recipe), format!("i{}", edge.ingredient)),
+            };
+            writeln!(dot, "    {} -> {} [label=\"{}\"];", from, to, escape_dot(&self.edge_label(edge))).unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let (machines, classes) = self.machine_classes();
+        let mut mermaid = String::new();
+        writeln!(mermaid, "flowchart LR").unwrap();
+        for (index, ingredient) in self.ingredients.iter().enumerate() {
+            match ingredient.kind {
+                IngredientKind::Item => writeln!(mermaid, "    i{}([\"{}\"])", index, escape_mermaid(&ingredient.name)),
+                IngredientKind::Fluid => writeln!(mermaid, "    i{}[(\"{}\")]", index, escape_mermaid(&ingredient.name)),
+            }
+            .unwrap();
+        }
+        for (index, recipe) in self.recipes.iter().enumerate() {
+            let label = self
+                .recipe_label(recipe)
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect::<Vec<String>>()
+                .join("<br/>");
+            writeln!(mermaid, "    r{}[\"{}\"]", index, label).unwrap();
+        }
+        for edge in &self.edges {
+            let label = escape_mermaid(&self.edge_label(edge));
+            match edge.direction {
+                EdgeDirection::Input => writeln!(mermaid, "    i{} -->|\"{}\"| r{}", edge.ingredient, label, edge.recipe),
+                EdgeDirection::Output => writeln!(mermaid, "    r{} -->|\"{}\"| i{}", edge.recipe, label, edge.ingredient),
+            }
+            .unwrap();
+        }
+        for (index, _) in machines.iter().enumerate() {
+            writeln!(mermaid, "    classDef machine{} fill:{}", index, MACHINE_COLORS[index % MACHINE_COLORS.len()]).unwrap();
+        }
+        for (index, class) in classes.iter().enumerate() {
+            writeln!(mermaid, "    class r{} machine{}", index, class).unwrap();
+        }
+        mermaid
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ingredients::fluid::Fluid;
+    use crate::types::ingredients::item::Item;
+
+    fn electrolysis() -> GregtechRecipe {
+        GregtechRecipe {
+            enabled: true,
+            duration: 100,
+            eut: 30,
+            item_inputs: vec![Item {
+                amount: 1,
+                unlocalized_name: Some("ic2.itemCellEmpty".to_string()),
+                localized_name: Some("Empty Cell".to_string()),
+            }],
+            item_outputs: vec![],
+            fluid_inputs: vec![Fluid {
+                amount: 3000,
+                unlocalized_name: Some("water".to_string()),
+                localized_name: Some("Water".to_string()),
+            }],
+            fluid_outputs: vec![
+                Fluid {
+                    amount: 2000,
+                    unlocalized_name: Some("hydrogen".to_string()),
+                    localized_name: Some("Hydrogen Gas".to_string()),
+                },
+                Fluid {
+                    amount: 1000,
+                    unlocalized_name: Some("oxygen".to_string()),
+                    localized_name: Some("Oxygen Gas".to_string()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_graph_nodes() {
+        let graph = RecipeGraph::from_recipes(&[
+            ("Electrolyzer".to_string(), electrolysis()),
+            ("Electrolyzer".to_string(), electrolysis()),
+        ]);
+        assert_eq!(graph.recipes.len(), 2);
+        assert_eq!(graph.ingredients.len(), 4);
+        assert_eq!(graph.edges.len(), 8);
+    }
+
+    #[test]
+    fn test_dot() {
+        let graph = RecipeGraph::from_recipes(&[("Electrolyzer".to_string(), electrolysis())]);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph recipes {"));
+        assert!(dot.contains("i1 [label=\"Water\", shape=ellipse, style=dashed];"));
+        assert!(dot.contains("i1 -> r0 [label=\"3000l\"];"));
+        assert!(dot.contains("r0 -> i2 [label=\"2000l\"];"));
+    }
+
+    #[test]
+    fn test_mermaid_rates() {
+        let graph = RecipeGraph::from_rated_recipes(&[("Electrolyzer".to_string(), electrolysis(), 0.5)]);
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("i0 -->|\"0.50x/s\"| r0"));
+        assert!(mermaid.contains("r0 -->|\"1000.00l/s\"| i2"));
+        assert!(mermaid.contains("class r0 machine0"));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod card;
+pub mod flow;
+pub mod graph;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod table;
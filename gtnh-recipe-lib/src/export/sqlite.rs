@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::path::Path;
+use rusqlite::{params, Connection, Transaction};
+use crate::types::json::JsonFormat;
+
+const SCHEMA: &str = "
+CREATE TABLE machines (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE recipes (
+    id INTEGER PRIMARY KEY,
+    machine_id INTEGER NOT NULL REFERENCES machines(id),
+    enabled INTEGER NOT NULL,
+    duration INTEGER NOT NULL,
+    eut INTEGER NOT NULL
+);
+CREATE TABLE items (
+    id INTEGER PRIMARY KEY,
+    unlocalized_name TEXT,
+    localized_name TEXT
+);
+CREATE TABLE fluids (
+    id INTEGER PRIMARY KEY,
+    unlocalized_name TEXT,
+    localized_name TEXT
+);
+CREATE TABLE recipe_item_inputs (
+    recipe_id INTEGER NOT NULL REFERENCES recipes(id),
+    item_id INTEGER NOT NULL REFERENCES items(id),
+    position INTEGER NOT NULL,
+    amount INTEGER NOT NULL
+);
+CREATE TABLE recipe_item_outputs (
+    recipe_id INTEGER NOT NULL REFERENCES recipes(id),
+    item_id INTEGER NOT NULL REFERENCES items(id),
+    position INTEGER NOT NULL,
+    amount INTEGER NOT NULL
+);
+CREATE TABLE recipe_fluid_inputs (
+    recipe_id INTEGER NOT NULL REFERENCES recipes(id),
+    fluid_id INTEGER NOT NULL REFERENCES fluids(id),
+    position INTEGER NOT NULL,
+    amount INTEGER NOT NULL
+);
+CREATE TABLE recipe_fluid_outputs (
+    recipe_id INTEGER NOT NULL REFERENCES recipes(id),
+    fluid_id INTEGER NOT NULL REFERENCES fluids(id),
+    position INTEGER NOT NULL,
+    amount INTEGER NOT NULL
+);
+";
+
+// Created after inserting, which is a lot faster than updating them row by row
+const INDEXES: &str = "
+CREATE INDEX idx_recipes_machine ON recipes(machine_id);
+CREATE INDEX idx_items_unlocalized_name ON items(unlocalized_name);
+CREATE INDEX idx_items_localized_name ON items(localized_name);
+CREATE INDEX idx_fluids_unlocalized_name ON fluids(unlocalized_name);
+CREATE INDEX idx_fluids_localized_name ON fluids(localized_name);
+CREATE INDEX idx_recipe_item_inputs_recipe ON recipe_item_inputs(recipe_id);
+CREATE INDEX idx_recipe_item_inputs_item ON recipe_item_inputs(item_id);
+CREATE INDEX idx_recipe_item_outputs_recipe ON recipe_item_outputs(recipe_id);
+CREATE INDEX idx_recipe_item_outputs_item ON recipe_item_outputs(item_id);
+CREATE INDEX idx_recipe_fluid_inputs_recipe ON recipe_fluid_inputs(recipe_id);
+CREATE INDEX idx_recipe_fluid_inputs_fluid ON recipe_fluid_inputs(fluid_id);
+CREATE INDEX idx_recipe_fluid_outputs_recipe ON recipe_fluid_outputs(recipe_id);
+CREATE INDEX idx_recipe_fluid_outputs_fluid ON recipe_fluid_outputs(fluid_id);
+";
+
+type IngredientKey = (Option<String>, Option<String>);
+
+/// Writes all machine recipes into the SQLite database at `path`, which must not contain the recipe tables yet.
+pub fn export_sqlite(recipes: &JsonFormat, path: &Path) -> rusqlite::Result<()> {
+    let mut connection = Connection::open(path)?;
+    write_database(&mut connection, recipes)
+}
+
+/// Creates the recipe tables in an empty database and fills them.
+pub fn write_database(connection: &mut Connection, recipes: &JsonFormat) -> rusqlite::Result<()> {
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+
+    let mut machine_ids: HashMap<String, i64> = HashMap::new();
+    let mut item_ids: HashMap<IngredientKey, i64> = HashMap::new();
+    let mut fluid_ids: HashMap<IngredientKey, i64> = HashMap::new();
+
+    for source in &recipes.sources {
+        for machine in &source.machines {
+            let machine_id = match machine_ids.get(&machine.name) {
+                Some(id) => *id,
+                None => {
+                    transaction.execute("INSERT INTO machines (name) VALUES (?1)", params![machine.name])?;
+                    let id = transaction.last_insert_rowid();
+                    machine_ids.insert(machine.name.clone(), id);
+                    id
+                }
+            };
+
+            for recipe in &machine.recipes {
+                transaction.execute(
+                    "INSERT INTO recipes (machine_id, enabled, duration, eut) VALUES (?1, ?2, ?3, ?4)",
+                    params![machine_id, recipe.enabled, recipe.duration as i64, recipe.eut as i64],
+                )?;
+                let recipe_id = transaction.last_insert_rowid();
+
+                for (position, item) in recipe.item_inputs.iter().enumerate() {
+                    let item_id = ingredient_id(&transaction, "items", &mut item_ids, &item.unlocalized_name, &item.localized_name)?;
+                    insert_ingredient(&transaction, "recipe_item_inputs", "item_id", recipe_id, item_id, position, item.amount)?;
+                }
+                for (position, item) in recipe.item_outputs.iter().enumerate() {
+                    let item_id = ingredient_id(&transaction, "items", &mut item_ids, &item.unlocalized_name, &item.localized_name)?;
+                    insert_ingredient(&transaction, "recipe_item_outputs", "item_id", recipe_id, item_id, position, item.amount)?;
+                }
+                for (position, fluid) in recipe.fluid_inputs.iter().enumerate() {
+                    let fluid_id = ingredient_id(&transaction, "fluids", &mut fluid_ids, &fluid.unlocalized_name, &fluid.localized_name)?;
+                    insert_ingredient(&transaction, "recipe_fluid_inputs", "fluid_id", recipe_id, fluid_id, position, fluid.amount)?;
+                }
+                for (position, fluid) in recipe.fluid_outputs.iter().enumerate() {
+                    let fluid_id = ingredient_id(&transaction, "fluids", &mut fluid_ids, &fluid.unlocalized_name, &fluid.localized_name)?;
+                    insert_ingredient(&transaction, "recipe_fluid_outputs", "fluid_id", recipe_id, fluid_id, position, fluid.amount)?;
+                }
+            }
+        }
+    }
+
+    transaction.execute_batch(INDEXES)?;
+    transaction.commit()
+}
+
+fn ingredient_id(
+    transaction: &Transaction<'_>,
+    table: &str,
+    ids: &mut HashMap<IngredientKey, i64>,
+    unlocalized_name: &Option<String>,
+    localized_name: &Option<String>,
+) -> rusqlite::Result<i64> {
+    let key = (unlocalized_name.clone(), localized_name.clone());
+    if let Some(id) = ids.get(&key) {
+        return Ok(*id);
+    }
+    transaction
+        .prepare_cached(&format!("INSERT INTO {} (unlocalized_name, localized_name) VALUES (?1, ?2)", table))?
+        .execute(params![unlocalized_name, localized_name])?;
+    let id = transaction.last_insert_rowid();
+    ids.insert(key, id);
+    Ok(id)
+}
+
+fn insert_ingredient(
+    transaction: &Transaction<'_>,
+    table: &str,
+    id_column: &str,
+    recipe_id: i64,
+    ingredient_id: i64,
+    position: usize,
+    amount: usize,
+) -> rusqlite::Result<()> {
+    transaction
+        .prepare_cached(&format!(
+            "INSERT INTO {} (recipe_id, {}, position, amount) VALUES (?1, ?2, ?3, ?4)",
+            table, id_column
+        ))?
+        .execute(params![recipe_id, ingredient_id, position as i64, amount as i64])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_database() {
+        let recipes = crate::load_file(&std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../recipes_small.json")));
+        let mut connection = Connection::open_in_memory().unwrap();
+        write_database(&mut connection, &recipes).unwrap();
+
+        let recipe_count: usize = connection
+            .query_row("SELECT COUNT(*) FROM recipes", [], |row| row.get(0))
+            .unwrap();
+        let machine_recipe_count: usize = recipes.sources.iter()
+            .flat_map(|source| &source.machines)
+            .map(|machine| machine.recipes.len())
+            .sum();
+        assert_eq!(recipe_count, machine_recipe_count);
+
+        let electrolyzer_recipes: usize = connection
+            .query_row(
+                "SELECT COUNT(*) FROM recipes JOIN machines ON machines.id = recipes.machine_id WHERE machines.name = ?1",
+                ["Electrolyzer"],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(electrolyzer_recipes, recipes.recipes_of_machine("Electrolyzer").len());
+
+        let redstone_uses: usize = connection
+            .query_row(
+                "SELECT COUNT(DISTINCT recipe_id) FROM recipe_item_inputs
+                 JOIN items ON items.id = recipe_item_inputs.item_id WHERE items.localized_name = 'Redstone'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(redstone_uses > 0);
+    }
+}
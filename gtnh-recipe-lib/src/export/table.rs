@@ -0,0 +1,100 @@
+use crate::types::gregtech_recipe::GregtechRecipe;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+}
+
+impl TableFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Tsv => "tsv",
+        }
+    }
+
+    fn delimiter(&self) -> u8 {
+        match self {
+            TableFormat::Csv => b',',
+            TableFormat::Tsv => b'\t',
+        }
+    }
+}
+
+pub const TABLE_HEADER: [&str; 7] = [
+    "Machine",
+    "Items (Input)",
+    "Fluids (Input)",
+    "Items (Output)",
+    "Fluids (Output)",
+    "Duration (ticks)",
+    "EU/t",
+];
+
+/// Serializes recipes with one row per recipe, joining ingredients of a column with " + ".
+pub fn recipes_to_table(recipes: &[(String, GregtechRecipe)], format: TableFormat) -> Result<String, csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(format.delimiter())
+        .from_writer(vec![]);
+    writer.write_record(TABLE_HEADER)?;
+    for (machine_name, recipe) in recipes {
+        writer.write_record([
+            machine_name.clone(),
+            recipe.item_inputs.iter().map(|item| format!("{}", item)).collect::<Vec<String>>().join(" + "),
+            recipe.fluid_inputs.iter().map(|fluid| format!("{}", fluid)).collect::<Vec<String>>().join(" + "),
+            recipe.item_outputs.iter().map(|item| format!("{}", item)).collect::<Vec<String>>().join(" + "),
+            recipe.fluid_outputs.iter().map(|fluid| format!("{}", fluid)).collect::<Vec<String>>().join(" + "),
+            recipe.duration.to_string(),
+            recipe.eut.to_string(),
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("csv writer produced invalid utf-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ingredients::item::Item;
+
+    #[test]
+    fn test_recipes_to_table() {
+        let recipe = GregtechRecipe {
+            enabled: true,
+            duration: 200,
+            eut: 30,
+            item_inputs: vec![
+                Item {
+                    amount: 4,
+                    unlocalized_name: Some("gt.metaitem.01.18305".to_string()),
+                    localized_name: Some("Double Steel Plate".to_string()),
+                },
+                Item {
+                    amount: 4,
+                    unlocalized_name: Some("gt.metaitem.01.28300".to_string()),
+                    localized_name: Some("Bronze Ring".to_string()),
+                },
+            ],
+            item_outputs: vec![Item {
+                amount: 1,
+                unlocalized_name: Some("gt.metaitem.01.32405".to_string()),
+                localized_name: Some("Large Steel Fluid Cell, \"big\"".to_string()),
+            }],
+            fluid_inputs: vec![],
+            fluid_outputs: vec![],
+        };
+        let recipes = vec![("Assembler".to_string(), recipe)];
+
+        let csv = recipes_to_table(&recipes, TableFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("Machine,Items (Input),Fluids (Input),Items (Output),Fluids (Output),Duration (ticks),EU/t"));
+        assert_eq!(
+            lines.next(),
+            Some("Assembler,4x Double Steel Plate + 4x Bronze Ring,,\"1x Large Steel Fluid Cell, \"\"big\"\"\",,200,30")
+        );
+
+        let tsv = recipes_to_table(&recipes, TableFormat::Tsv).unwrap();
+        assert!(tsv.lines().nth(1).unwrap().starts_with("Assembler\t4x Double Steel Plate + 4x Bronze Ring\t\t"));
+    }
+}
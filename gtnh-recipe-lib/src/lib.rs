@@ -1,21 +1,19 @@
-use std::fs::File;
-use std::io::Read;
 use std::path::PathBuf;
 
+pub mod export;
+pub mod load;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod types;
 
+/// Loads a JSON dump or binary cache, panicking if it can't be read.
 pub fn load_file(path: &PathBuf) -> types::json::JsonFormat {
-    let mut file = File::open(path).unwrap();
-    let mut file_str = String::new();
-    file.read_to_string(&mut file_str)
-        .expect("unable to read file to string");
-    let deserialized: types::json::JsonFormat = serde_json::from_str(&file_str).unwrap();
-    deserialized
+    let bytes = std::fs::read(path).expect("unable to read file");
+    load_bytes(bytes)
 }
 
 pub fn load_bytes(bytes: Vec<u8>) -> types::json::JsonFormat {
-    let deserialized: types::json::JsonFormat = serde_json::from_slice(bytes.as_slice()).unwrap();
-    deserialized
+    load::try_load_bytes(&bytes).unwrap()
 }
 
 pub fn add(left: usize, right: usize) -> usize {
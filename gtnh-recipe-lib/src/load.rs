@@ -0,0 +1,219 @@
+use std::fmt::{Display, Formatter};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use crate::types::gregtech_machine::GregtechMachine;
+use crate::types::json::{JsonFormat, Source};
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Binary(rmp_serde::decode::Error),
+    Cancelled,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "unable to read recipes: {}", err),
+            LoadError::Json(err) => write!(f, "invalid recipe dump: {}", err),
+            LoadError::Binary(err) => write!(f, "invalid binary recipe cache: {}", err),
+            LoadError::Cancelled => write!(f, "loading was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(err: serde_json::Error) -> Self {
+        LoadError::Json(err)
+    }
+}
+
+impl From<rmp_serde::decode::Error> for LoadError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        LoadError::Binary(err)
+    }
+}
+
+/// Start of a binary recipe cache, followed by the MessagePack encoded dump
+pub const BINARY_MAGIC: &[u8] = b"GTNHRCP\x01";
+
+/// Encodes a dump as binary cache, which loads a lot faster than the JSON exported by RecEx.
+pub fn to_binary(recipes: &JsonFormat) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    let mut bytes = BINARY_MAGIC.to_vec();
+    rmp_serde::encode::write_named(&mut bytes, recipes)?;
+    Ok(bytes)
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(BINARY_MAGIC)
+}
+
+pub fn from_binary(bytes: &[u8]) -> Result<JsonFormat, LoadError> {
+    match bytes.strip_prefix(BINARY_MAGIC) {
+        Some(encoded) => Ok(rmp_serde::from_slice(encoded)?),
+        None => Err(LoadError::Binary(rmp_serde::decode::Error::Uncategorized("missing binary cache header".to_string()))),
+    }
+}
+
+/// A source whose machines are not parsed yet
+#[derive(Deserialize)]
+struct PendingSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    #[serde(default)]
+    recipes: Vec<serde_json::Value>,
+    #[serde(default)]
+    machines: Vec<Box<RawValue>>,
+}
+
+#[derive(Deserialize)]
+struct PendingDump {
+    sources: Vec<PendingSource>,
+}
+
+/// Parses a recipe dump one machine at a time, so callers can show progress, cancel, or
+/// yield to an event loop between steps.
+pub struct IncrementalLoader {
+    pending: Vec<PendingSource>,
+    /// Index of the source and machine to parse next
+    next: (usize, usize),
+    machines_total: usize,
+    machines_done: usize,
+    loaded: JsonFormat,
+}
+
+impl IncrementalLoader {
+    /// Checks the structure of the dump, leaving the machines for `step`.
+    pub fn new(bytes: &[u8]) -> Result<Self, LoadError> {
+        let mut dump: PendingDump = serde_json::from_slice(bytes)?;
+        let machines_total = dump.sources.iter().map(|source| source.machines.len()).sum();
+        let loaded = JsonFormat {
+            sources: dump
+                .sources
+                .iter_mut()
+                .map(|source| Source {
+                    source_type: source.source_type.clone(),
+                    recipes: std::mem::take(&mut source.recipes),
+                    machines: Vec::with_capacity(source.machines.len()),
+                })
+                .collect(),
+        };
+        Ok(IncrementalLoader {
+            pending: dump.sources,
+            next: (0, 0),
+            machines_total,
+            machines_done: 0,
+            loaded,
+        })
+    }
+
+    /// Fraction of machines parsed so far.
+    pub fn progress(&self) -> f32 {
+        if self.machines_total == 0 {
+            1.0
+        } else {
+            self.machines_done as f32 / self.machines_total as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.machines_done >= self.machines_total
+    }
+
+    /// Parses the next machine.
+    pub fn step(&mut self) -> Result<(), LoadError> {
+        while let Some(pending) = self.pending.get_mut(self.next.0) {
+            let (source, machine) = self.next;
+            let Some(raw) = pending.machines.get_mut(machine) else {
+                self.next = (source + 1, 0);
+                continue;
+            };
+            let parsed: GregtechMachine = serde_json::from_str(raw.get())?;
+            // Free the raw text of parsed machines early, dumps can be hundreds of megabytes
+            *raw = RawValue::from_string("null".to_string())?;
+            self.loaded.sources[source].machines.push(parsed);
+            self.machines_done += 1;
+            self.next = (source, machine + 1);
+            break;
+        }
+        Ok(())
+    }
+
+    /// Parses the remaining machines, calling `progress` after each one. Stops with
+    /// `LoadError::Cancelled` once `progress` returns false.
+    pub fn run(mut self, mut progress: impl FnMut(f32) -> bool) -> Result<JsonFormat, LoadError> {
+        while !self.is_finished() {
+            self.step()?;
+            if !progress(self.progress()) {
+                return Err(LoadError::Cancelled);
+            }
+        }
+        Ok(self.finish())
+    }
+
+    pub fn finish(self) -> JsonFormat {
+        self.loaded
+    }
+}
+
+/// Parses a JSON dump or binary cache without panicking on invalid input.
+pub fn try_load_bytes(bytes: &[u8]) -> Result<JsonFormat, LoadError> {
+    if is_binary(bytes) {
+        from_binary(bytes)
+    } else {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../recipes_small.json");
+
+    #[test]
+    fn test_incremental_load() {
+        let bytes = std::fs::read(FIXTURE).unwrap();
+        let mut reported = vec![];
+        let loaded = IncrementalLoader::new(&bytes).unwrap().run(|progress| {
+            reported.push(progress);
+            true
+        }).unwrap();
+
+        assert_eq!(loaded.get_recipe_count(), try_load_bytes(&bytes).unwrap().get_recipe_count());
+        assert_eq!(reported.len(), loaded.sources.iter().map(|source| source.machines.len()).sum::<usize>());
+        assert_eq!(reported.last(), Some(&1.0));
+
+        let cancelled = IncrementalLoader::new(&bytes).unwrap().run(|progress| progress < 0.5);
+        assert!(matches!(cancelled, Err(LoadError::Cancelled)));
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let recipes = try_load_bytes(&std::fs::read(FIXTURE).unwrap()).unwrap();
+        let bytes = to_binary(&recipes).unwrap();
+        assert!(is_binary(&bytes));
+
+        let loaded = try_load_bytes(&bytes).unwrap();
+        assert_eq!(loaded.get_recipe_count(), recipes.get_recipe_count());
+        assert_eq!(loaded.sources[0].machines[0].recipes, recipes.sources[0].machines[0].recipes);
+        assert!(matches!(from_binary(b"{}"), Err(LoadError::Binary(_))));
+    }
+
+    #[test]
+    fn test_invalid_dump() {
+        assert!(matches!(IncrementalLoader::new(b"{\"sources\": 1}"), Err(LoadError::Json(_))));
+        let bytes = br#"{"sources": [{"type": "gregtech", "machines": [{"n": "Mixer"}]}]}"#;
+        let result = IncrementalLoader::new(bytes).unwrap().run(|_| true);
+        assert!(matches!(result, Err(LoadError::Json(_))));
+    }
+}
@@ -0,0 +1,68 @@
+//! Recipes for tests of this crate and the tools using it, enabled by the `test-util` feature.
+
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::ingredients::fluid::Fluid;
+use crate::types::ingredients::item::Item;
+
+/// Item with only a localized name
+pub fn item(name: &str, amount: usize) -> Item {
+    Item {
+        amount,
+        unlocalized_name: None,
+        localized_name: Some(name.to_string()),
+    }
+}
+
+/// Fluid with only a localized name
+pub fn fluid(name: &str, amount: usize) -> Fluid {
+    Fluid {
+        amount,
+        unlocalized_name: None,
+        localized_name: Some(name.to_string()),
+    }
+}
+
+/// Builds an enabled recipe, ingredients are given as pairs of localized name and amount.
+pub struct RecipeBuilder {
+    recipe: GregtechRecipe,
+}
+
+impl RecipeBuilder {
+    pub fn new(eut: usize, duration: usize) -> Self {
+        RecipeBuilder {
+            recipe: GregtechRecipe {
+                enabled: true,
+                duration,
+                eut,
+                item_inputs: vec![],
+                item_outputs: vec![],
+                fluid_inputs: vec![],
+                fluid_outputs: vec![],
+            },
+        }
+    }
+
+    pub fn item_inputs(mut self, items: &[(&str, usize)]) -> Self {
+        self.recipe.item_inputs.extend(items.iter().map(|(name, amount)| item(name, *amount)));
+        self
+    }
+
+    pub fn item_outputs(mut self, items: &[(&str, usize)]) -> Self {
+        self.recipe.item_outputs.extend(items.iter().map(|(name, amount)| item(name, *amount)));
+        self
+    }
+
+    pub fn fluid_inputs(mut self, fluids: &[(&str, usize)]) -> Self {
+        self.recipe.fluid_inputs.extend(fluids.iter().map(|(name, amount)| fluid(name, *amount)));
+        self
+    }
+
+    pub fn fluid_outputs(mut self, fluids: &[(&str, usize)]) -> Self {
+        self.recipe.fluid_outputs.extend(fluids.iter().map(|(name, amount)| fluid(name, *amount)));
+        self
+    }
+
+    pub fn build(self) -> GregtechRecipe {
+        self.recipe
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::ingredients::IngredientKind;
+use crate::types::json::JsonFormat;
+use crate::types::recipe_id::RecipeId;
+
+/// Intermediate ingredients expanded at most, further ones are listed as raw materials
+pub const MAX_STEPS: usize = 500;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MaterialAmount {
+    pub name: String,
+    pub kind: IngredientKind,
+    pub amount: f64,
+}
+
+/// A recipe run to make an intermediate ingredient of the bill of materials.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MaterialStep {
+    /// Ingredient made by this step, `None` for the expanded recipe itself
+    pub product: Option<String>,
+    pub machine_name: String,
+    pub recipe: GregtechRecipe,
+    pub crafts: f64,
+    /// Longest chain of steps from the expanded recipe, which has depth 0
+    pub depth: usize,
+}
+
+/// Raw materials, energy and time needed for a recipe, expanding its inputs recursively.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BillOfMaterials {
+    /// The expanded recipe, then one step per intermediate ingredient, each after all steps needing it
+    pub steps: Vec<MaterialStep>,
+    /// Ingredients that are not expanded further, sorted by name
+    pub raw: Vec<MaterialAmount>,
+    /// Outputs of the intermediate steps besides the ingredient they are run for
+    pub byproducts: Vec<MaterialAmount>,
+    /// Energy of all steps in EU
+    pub eu: f64,
+    /// Duration of all steps run one after another on a single machine each, in ticks
+    pub ticks: f64,
+    /// Ingredients listed as raw because making them needs themselves
+    pub cycles: Vec<String>,
+    /// Whether expansion stopped at [`MAX_STEPS`]
+    pub truncated: bool,
+}
+
+/// Amount of `name` produced by a single craft of `recipe`.
+fn output_amount(recipe: &GregtechRecipe, name: &str) -> usize {
+    recipe.item_outputs.iter().filter(|item| item.has_name(name)).map(|item| item.amount).sum::<usize>()
+        + recipe.fluid_outputs.iter().filter(|fluid| fluid.has_name(name)).map(|fluid| fluid.amount).sum::<usize>()
+}
+
+fn inputs(recipe: &GregtechRecipe) -> Vec<(String, IngredientKind, usize)> {
+    recipe.item_inputs.iter().map(|item| (item.get_name(), IngredientKind::Item, item.amount))
+        .chain(recipe.fluid_inputs.iter().map(|fluid| (fluid.get_name(), IngredientKind::Fluid, fluid.amount)))
+        .collect()
+}
+
+fn outputs(recipe: &GregtechRecipe) -> Vec<(String, IngredientKind, usize)> {
+    recipe.item_outputs.iter().map(|item| (item.get_name(), IngredientKind::Item, item.amount))
+        .chain(recipe.fluid_outputs.iter().map(|fluid| (fluid.get_name(), IngredientKind::Fluid, fluid.amount)))
+        .collect()
+}
+
+/// Recipes able to make `name`, cheapest in EU per unit first.
+///
+/// Recipes consuming `name` themselves are left out, as they can't be used to make it.
+pub fn producers(recipes: &JsonFormat, name: &str) -> Vec<(String, GregtechRecipe)> {
+    let eu_per_unit = |recipe: &GregtechRecipe| (recipe.eut * recipe.duration) as f64 / output_amount(recipe, name).max(1) as f64;
+    let mut producers = recipes.recipes_producing(name);
+    producers.retain(|(_, recipe)| !recipe.uses(name) && output_amount(recipe, name) > 0);
+    producers.sort_by(|(machine_a, a), (machine_b, b)| {
+        eu_per_unit(a)
+            .total_cmp(&eu_per_unit(b))
+            .then((a.item_inputs.len() + a.fluid_inputs.len()).cmp(&(b.item_inputs.len() + b.fluid_inputs.len())))
+            .then(machine_a.cmp(machine_b))
+    });
+    producers
+}
+
+/// Producers picked for the intermediate ingredients of a bill of materials.
+struct Expansion<'a> {
+    recipes: &'a JsonFormat,
+    /// Producer picked by the user per ingredient name
+    choices: &'a BTreeMap<String, RecipeId>,
+    /// Ingredients the user wants to treat as raw materials
+    raw_names: &'a BTreeSet<String>,
+    /// Ingredients already looked at, whether they are expanded or not
+    seen: HashSet<String>,
+    /// Ingredients whose producer inputs are being visited, outermost first
+    path: Vec<String>,
+    /// Producer of every expanded ingredient
+    producers: HashMap<String, (String, GregtechRecipe)>,
+    /// Expanded ingredients, each after every ingredient its producer needs
+    order: Vec<String>,
+    /// Ingredients needed to make themselves, they are raw everywhere
+    cycles: Vec<String>,
+    truncated: bool,
+}
+
+impl Expansion<'_> {
+    fn producer(&self, name: &str) -> Option<(String, GregtechRecipe)> {
+        let chosen = self.choices.get(name).and_then(|id| self.recipes.recipe_by_id(id));
+        chosen.or_else(|| producers(self.recipes, name).into_iter().next())
+    }
+
+    /// Picks producers for the inputs of `recipe` and recursively for theirs.
+    fn visit(&mut self, recipe: &GregtechRecipe) {
+        for (name, _, _) in consumed(recipe) {
+            if self.path.contains(&name) {
+                if !self.cycles.contains(&name) {
+                    self.cycles.push(name);
+                }
+                continue;
+            }
+            if !self.seen.insert(name.clone()) || self.raw_names.contains(&name) {
+                continue;
+            }
+            if self.producers.len() + self.path.len() >= MAX_STEPS {
+                self.truncated = true;
+                continue;
+            }
+            let Some((machine_name, producer)) = self.producer(&name) else {
+                continue;
+            };
+            self.path.push(name.clone());
+            self.visit(&producer);
+            self.path.pop();
+            if !self.cycles.contains(&name) {
+                self.producers.insert(name.clone(), (machine_name, producer));
+                self.order.push(name);
+            }
+        }
+    }
+}
+
+/// Inputs used up by `recipe`, amounts of zero are catalysts like programmed circuits.
+fn consumed(recipe: &GregtechRecipe) -> Vec<(String, IngredientKind, usize)> {
+    inputs(recipe).into_iter().filter(|(_, _, amount)| *amount > 0).collect()
+}
+
+/// Running totals while the steps are added in order.
+#[derive(Default)]
+struct Totals {
+    bill: BillOfMaterials,
+    /// Amount needed and depth per expanded ingredient
+    demand: HashMap<String, (f64, usize)>,
+    raw: BTreeMap<String, (IngredientKind, f64)>,
+    byproducts: BTreeMap<String, (IngredientKind, f64)>,
+}
+
+impl Totals {
+    fn run(&mut self, expansion: &Expansion, product: Option<&str>, machine_name: &str, recipe: &GregtechRecipe, crafts: f64, depth: usize) {
+        self.bill.steps.push(MaterialStep {
+            product: product.map(str::to_string),
+            machine_name: machine_name.to_string(),
+            recipe: recipe.clone(),
+            crafts,
+            depth,
+        });
+        self.bill.eu += crafts * (recipe.eut * recipe.duration) as f64;
+        self.bill.ticks += crafts * recipe.duration as f64;
+        if let Some(product) = product {
+            for (name, kind, amount) in outputs(recipe).into_iter().filter(|(name, _, _)| name != product) {
+                self.byproducts.entry(name).or_insert((kind, 0.0)).1 += amount as f64 * crafts;
+            }
+        }
+        for (name, kind, amount) in consumed(recipe) {
+            let amount = amount as f64 * crafts;
+            if expansion.producers.contains_key(&name) {
+                let demand = self.demand.entry(name).or_insert((0.0, 0));
+                demand.0 += amount;
+                demand.1 = demand.1.max(depth + 1);
+            } else {
+                self.raw.entry(name).or_insert((kind, 0.0)).1 += amount;
+            }
+        }
+    }
+}
+
+fn amounts(amounts: BTreeMap<String, (IngredientKind, f64)>) -> Vec<MaterialAmount> {
+    amounts.into_iter().map(|(name, (kind, amount))| MaterialAmount { name, kind, amount }).collect()
+}
+
+impl BillOfMaterials {
+    /// Expands `crafts` runs of `recipe` down to raw materials.
+    ///
+    /// Inputs are made with the recipe chosen in `choices` (by ingredient name), or else the
+    /// cheapest one from [`producers`]. Ingredients in `raw`, without producer or needed to make
+    /// themselves are not expanded. Every ingredient is made by a single step for the total
+    /// amount needed, at most [`MAX_STEPS`] of them.
+    pub fn expand(
+        recipes: &JsonFormat,
+        machine_name: &str,
+        recipe: &GregtechRecipe,
+        crafts: f64,
+        choices: &BTreeMap<String, RecipeId>,
+        raw: &BTreeSet<String>,
+    ) -> Self {
+        let mut expansion = Expansion {
+            recipes,
+            choices,
+            raw_names: raw,
+            seen: HashSet::new(),
+            path: vec![],
+            producers: HashMap::new(),
+            order: vec![],
+            cycles: vec![],
+            truncated: false,
+        };
+        expansion.visit(recipe);
+
+        // Consumers come before the ingredients they need, so all demand is known when a step runs.
+        // Ingredients only needed to make an ingredient that turned out to be raw have no demand.
+        let mut totals = Totals::default();
+        totals.run(&expansion, None, machine_name, recipe, crafts, 0);
+        for name in expansion.order.iter().rev() {
+            let Some(&(amount, depth)) = totals.demand.get(name) else {
+                continue;
+            };
+            let (machine_name, producer) = &expansion.producers[name];
+            totals.run(&expansion, Some(name), machine_name, producer, amount / output_amount(producer, name) as f64, depth);
+        }
+        BillOfMaterials {
+            raw: amounts(totals.raw),
+            byproducts: amounts(totals.byproducts),
+            cycles: expansion.cycles,
+            truncated: expansion.truncated,
+            ..totals.bill
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::RecipeBuilder;
+    use crate::types::gregtech_machine::GregtechMachine;
+    use crate::types::json::Source;
+
+    fn dump() -> JsonFormat {
+        let machine = |name: &str, recipe: RecipeBuilder| GregtechMachine { name: name.to_string(), recipes: vec![recipe.build()] };
+        JsonFormat {
+            sources: vec![Source {
+                source_type: "gregtech".to_string(),
+                recipes: vec![],
+                machines: vec![
+                    machine("Assembler", RecipeBuilder::new(30, 100).item_inputs(&[("Steel Plate", 4), ("Circuit", 0)]).item_outputs(&[("Hull", 1)])),
+                    machine("Bender", RecipeBuilder::new(24, 50).item_inputs(&[("Steel Ingot", 1)]).item_outputs(&[("Steel Plate", 1)])),
+                    // More EU per plate than bending
+                    machine("Forge Hammer", RecipeBuilder::new(16, 150).item_inputs(&[("Steel Ingot", 3)]).item_outputs(&[("Steel Plate", 2)])),
+                    machine("Blast Furnace", RecipeBuilder::new(120, 500).item_inputs(&[("Iron Ingot", 1), ("Oxygen Cell", 1)]).item_outputs(&[("Steel Ingot", 1), ("Empty Cell", 1)])),
+                    // Makes iron from itself, must not be expanded
+                    machine("Multiplier", RecipeBuilder::new(2, 10).item_inputs(&[("Iron Ingot", 1)]).item_outputs(&[("Iron Ingot", 2)])),
+                    machine("Electrolyzer", RecipeBuilder::new(30, 40).item_inputs(&[("Rust", 1)]).item_outputs(&[("Iron Ingot", 1)])),
+                    machine("Oxidizer", RecipeBuilder::new(8, 20).item_inputs(&[("Iron Ingot", 2)]).item_outputs(&[("Rust", 1)])),
+                    // Needs steel ingots directly and through the plate
+                    machine("Lathe", RecipeBuilder::new(16, 80).item_inputs(&[("Steel Plate", 1), ("Steel Ingot", 2)]).item_outputs(&[("Steel Rod", 1)])),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_producers() {
+        let recipes = dump();
+        let producers = producers(&recipes, "Steel Plate");
+        assert_eq!(producers.iter().map(|(machine, _)| machine.as_str()).collect::<Vec<_>>(), ["Bender", "Forge Hammer"]);
+        assert!(super::producers(&recipes, "Iron Ingot").iter().all(|(machine, _)| machine != "Multiplier"));
+    }
+
+    #[test]
+    fn test_expand() {
+        let recipes = dump();
+        let (machine_name, hull) = recipes.recipes_producing("Hull").remove(0);
+        let raw = BTreeSet::from(["Iron Ingot".to_string()]);
+        let bill = BillOfMaterials::expand(&recipes, &machine_name, &hull, 2.0, &BTreeMap::new(), &raw);
+
+        let steps = bill.steps.iter().map(|step| (step.machine_name.as_str(), step.crafts, step.depth)).collect::<Vec<_>>();
+        assert_eq!(steps, [("Assembler", 2.0, 0), ("Bender", 8.0, 1), ("Blast Furnace", 8.0, 2)]);
+        let raw = bill.raw.iter().map(|material| (material.name.as_str(), material.amount)).collect::<Vec<_>>();
+        // The programmed circuit is not used up
+        assert_eq!(raw, [("Iron Ingot", 8.0), ("Oxygen Cell", 8.0)]);
+        assert_eq!(bill.byproducts, [MaterialAmount { name: "Empty Cell".to_string(), kind: IngredientKind::Item, amount: 8.0 }]);
+        assert_eq!(bill.eu, 2.0 * 3000.0 + 8.0 * 1200.0 + 8.0 * 60000.0);
+        assert_eq!(bill.ticks, 2.0 * 100.0 + 8.0 * 50.0 + 8.0 * 500.0);
+        assert!(bill.cycles.is_empty());
+
+        // Choosing the forge hammer and expanding iron, which is made from rust made from iron
+        let choices = BTreeMap::from([("Steel Plate".to_string(), recipes.recipes_producing("Steel Plate")[1].1.id("Forge Hammer"))]);
+        let bill = BillOfMaterials::expand(&recipes, &machine_name, &hull, 1.0, &choices, &BTreeSet::new());
+        let steps = bill.steps.iter().map(|step| (step.machine_name.as_str(), step.crafts, step.depth)).collect::<Vec<_>>();
+        assert_eq!(steps, [("Assembler", 1.0, 0), ("Forge Hammer", 2.0, 1), ("Blast Furnace", 6.0, 2)]);
+        assert_eq!(bill.cycles, ["Iron Ingot"]);
+        let raw = bill.raw.iter().map(|material| (material.name.as_str(), material.amount)).collect::<Vec<_>>();
+        assert_eq!(raw, [("Iron Ingot", 6.0), ("Oxygen Cell", 6.0)]);
+        assert_eq!(bill.eu, 3000.0 + 2.0 * 2400.0 + 6.0 * 60000.0);
+    }
+
+    #[test]
+    fn test_expand_shared_ingredient() {
+        let recipes = dump();
+        let (machine_name, rod) = recipes.recipes_producing("Steel Rod").remove(0);
+        let raw = BTreeSet::from(["Iron Ingot".to_string()]);
+        let bill = BillOfMaterials::expand(&recipes, &machine_name, &rod, 1.0, &BTreeMap::new(), &raw);
+
+        // A single blast furnace step for the ingots of the plate and of the rod
+        let steps = bill.steps.iter().map(|step| (step.machine_name.as_str(), step.crafts, step.depth)).collect::<Vec<_>>();
+        assert_eq!(steps, [("Lathe", 1.0, 0), ("Bender", 1.0, 1), ("Blast Furnace", 3.0, 2)]);
+        assert_eq!(bill.raw.iter().map(|material| (material.name.as_str(), material.amount)).collect::<Vec<_>>(), [("Iron Ingot", 3.0), ("Oxygen Cell", 3.0)]);
+        assert!(!bill.truncated);
+    }
+}
@@ -20,6 +20,24 @@ pub struct GregtechRecipe {
     pub fluid_outputs: Vec<Fluid>
 }
 
+impl GregtechRecipe {
+    pub fn produces(&self, name: &str) -> bool {
+        self.item_outputs.iter().any(|item| item.has_name(name))
+            || self.fluid_outputs.iter().any(|fluid| fluid.has_name(name))
+    }
+
+    pub fn uses(&self, name: &str) -> bool {
+        self.item_inputs.iter().any(|item| item.has_name(name))
+            || self.fluid_inputs.iter().any(|fluid| fluid.has_name(name))
+    }
+
+    /// Whether every ingredient has an unlocalized or localized name, recipes not read from a dump may lack both.
+    pub fn has_ingredient_names(&self) -> bool {
+        self.item_inputs.iter().chain(&self.item_outputs).all(|item| item.unlocalized_name.is_some() || item.localized_name.is_some())
+            && self.fluid_inputs.iter().chain(&self.fluid_outputs).all(|fluid| fluid.unlocalized_name.is_some() || fluid.localized_name.is_some())
+    }
+}
+
 impl Display for GregtechRecipe {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let item_inputs = self.item_inputs.iter()
@@ -81,23 +99,23 @@ impl Display for Recipes {
     }
 }
 
-pub fn filter_recipes_by_input_item(recipes: &Vec<GregtechRecipe>, item_name: &str) -> Vec<GregtechRecipe> {
+pub fn filter_recipes_by_input_item(recipes: &[GregtechRecipe], item_name: &str) -> Vec<GregtechRecipe> {
     recipes.iter()
         .filter(|recipe| recipe.item_inputs.iter().any(|item| item.localized_name.as_deref() == Some(item_name)))
         .cloned()
         .collect()
 }
-pub fn filter_recipes_by_output_item(recipes: &Vec<GregtechRecipe>, item_name: &str) -> Vec<GregtechRecipe> {
+pub fn filter_recipes_by_output_item(recipes: &[GregtechRecipe], item_name: &str) -> Vec<GregtechRecipe> {
     recipes.iter()
         .filter(|recipe| recipe.item_outputs.iter().any(|item| item.localized_name.as_deref() == Some(item_name)))
         .cloned()
         .collect()
 }
-pub fn filter_recipes_by_input_fluid(recipes: &Vec<GregtechRecipe>, fluid_name: &str, amount: Option<usize>) -> Vec<GregtechRecipe> {
+pub fn filter_recipes_by_input_fluid(recipes: &[GregtechRecipe], fluid_name: &str, amount: Option<usize>) -> Vec<GregtechRecipe> {
     recipes.iter()
         .filter(|recipe| recipe.fluid_inputs.iter().any(|fluid| {
-            if amount != None {
-                (fluid.localized_name.as_deref() == Some(fluid_name)) && (fluid.amount == amount.unwrap())
+            if let Some(amount) = amount {
+                (fluid.localized_name.as_deref() == Some(fluid_name)) && (fluid.amount == amount)
             } else {
                 fluid.localized_name.as_deref() == Some(fluid_name)
             }
@@ -105,11 +123,11 @@ pub fn filter_recipes_by_input_fluid(recipes: &Vec<GregtechRecipe>, fluid_name:
         .cloned()
         .collect()
 }
-pub fn filter_recipes_by_output_fluid(recipes: &Vec<GregtechRecipe>, fluid_name: &str, amount: Option<usize>) -> Vec<GregtechRecipe> {
+pub fn filter_recipes_by_output_fluid(recipes: &[GregtechRecipe], fluid_name: &str, amount: Option<usize>) -> Vec<GregtechRecipe> {
     recipes.iter()
         .filter(|recipe| recipe.fluid_outputs.iter().any(|fluid|{
-            if amount != None {
-                (fluid.localized_name.as_deref() == Some(fluid_name)) && (fluid.amount == amount.unwrap())
+            if let Some(amount) = amount {
+                (fluid.localized_name.as_deref() == Some(fluid_name)) && (fluid.amount == amount)
             } else {
                 fluid.localized_name.as_deref() == Some(fluid_name)
             }
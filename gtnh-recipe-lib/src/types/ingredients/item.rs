@@ -1,5 +1,6 @@
 use std::fmt::{Debug, Display, Formatter};
 use serde::{Deserialize, Serialize};
+use super::{display_name, NameDisplay};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Item {
@@ -20,8 +21,19 @@ impl Item {
         }
     }
     pub fn get_name(&self) -> String {
-        let name = self.localized_name.clone().unwrap_or(self.unlocalized_name.clone().unwrap_or("ERROR_NAME_NOT_FOUND".to_string()));
-        name
+        self.display_name(NameDisplay::Localized)
+    }
+    pub fn display_name(&self, display: NameDisplay) -> String {
+        display_name(self.unlocalized_name.as_deref(), self.localized_name.as_deref(), display)
+    }
+    /// Amount and name, like the `Display` implementation but with the given names.
+    pub fn display_with(&self, display: NameDisplay) -> String {
+        format!("{}x {}", self.amount, self.display_name(display))
+    }
+    /// Whether the unlocalized or localized name equals `name`, ignoring ASCII case.
+    pub fn has_name(&self, name: &str) -> bool {
+        self.unlocalized_name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name))
+            || self.localized_name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name))
     }
 }
 
@@ -1,2 +1,50 @@
+use serde::{Deserialize, Serialize};
+
 pub mod fluid;
 pub mod item;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IngredientKind {
+    Item,
+    Fluid,
+}
+
+/// Which names of items and fluids to show
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NameDisplay {
+    /// The in-game name, like "Iron Ingot"
+    #[default]
+    Localized,
+    /// The internal name, like "gt.metaitem.01.11032"
+    Unlocalized,
+    /// The localized name followed by the unlocalized one in brackets
+    Both,
+}
+
+/// Name to show for an ingredient with the given names, falling back to whichever name exists.
+pub fn display_name(unlocalized_name: Option<&str>, localized_name: Option<&str>, display: NameDisplay) -> String {
+    let (preferred, fallback) = match display {
+        NameDisplay::Localized | NameDisplay::Both => (localized_name, unlocalized_name),
+        NameDisplay::Unlocalized => (unlocalized_name, localized_name),
+    };
+    match (display, localized_name, unlocalized_name) {
+        (NameDisplay::Both, Some(localized), Some(unlocalized)) if localized != unlocalized => format!("{} ({})", localized, unlocalized),
+        _ => preferred.or(fallback).unwrap_or("ERROR_NAME_NOT_FOUND").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name() {
+        let (unlocalized, localized) = (Some("gt.metaitem.01.11032"), Some("Iron Ingot"));
+        assert_eq!(display_name(unlocalized, localized, NameDisplay::Localized), "Iron Ingot");
+        assert_eq!(display_name(unlocalized, localized, NameDisplay::Unlocalized), "gt.metaitem.01.11032");
+        assert_eq!(display_name(unlocalized, localized, NameDisplay::Both), "Iron Ingot (gt.metaitem.01.11032)");
+        assert_eq!(display_name(None, localized, NameDisplay::Unlocalized), "Iron Ingot");
+        assert_eq!(display_name(unlocalized, None, NameDisplay::Both), "gt.metaitem.01.11032");
+        assert_eq!(display_name(None, None, NameDisplay::Localized), "ERROR_NAME_NOT_FOUND");
+    }
+}
@@ -1,6 +1,8 @@
 use crate::types::gregtech_machine::GregtechMachine;
 use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::recipe_id::RecipeId;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use strsim::jaro_winkler;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -30,7 +32,77 @@ impl JsonFormat {
         count
     }
 
-    pub fn search(&self, keyword: &String) -> Vec<(String, GregtechRecipe)> {
+    /// Localized and unlocalized names of every fluid used in a machine recipe.
+    pub fn fluid_names(&self) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for source in &self.sources {
+            for machine in &source.machines {
+                for recipe in &machine.recipes {
+                    for fluid in recipe.fluid_inputs.iter().chain(recipe.fluid_outputs.iter()) {
+                        names.extend(fluid.unlocalized_name.iter().cloned());
+                        names.extend(fluid.localized_name.iter().cloned());
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    pub fn recipes_of_machine(&self, machine_name: &str) -> Vec<(String, GregtechRecipe)> {
+        self.sources
+            .iter()
+            .flat_map(|source| &source.machines)
+            .filter(|machine| machine.name == machine_name)
+            .flat_map(|machine| machine.recipes.iter().map(|recipe| (machine.name.clone(), recipe.clone())))
+            .collect()
+    }
+
+    /// Machine names with their number of recipes, sorted by name.
+    ///
+    /// Machines appearing in several sources are listed once.
+    pub fn machine_recipe_counts(&self) -> Vec<(String, usize)> {
+        let mut counts = BTreeMap::new();
+        for machine in self.sources.iter().flat_map(|source| &source.machines) {
+            *counts.entry(machine.name.clone()).or_insert(0) += machine.recipes.len();
+        }
+        counts.into_iter().collect()
+    }
+
+    /// All machine recipes with an item or fluid output called `name`.
+    pub fn recipes_producing(&self, name: &str) -> Vec<(String, GregtechRecipe)> {
+        self.machine_recipes_where(|recipe| recipe.produces(name))
+    }
+
+    /// All machine recipes with an item or fluid input called `name`.
+    pub fn recipes_using(&self, name: &str) -> Vec<(String, GregtechRecipe)> {
+        self.machine_recipes_where(|recipe| recipe.uses(name))
+    }
+
+    /// The machine recipe with the given id, see `RecipeId`.
+    pub fn recipe_by_id(&self, id: &RecipeId) -> Option<(String, GregtechRecipe)> {
+        self.sources
+            .iter()
+            .flat_map(|source| &source.machines)
+            .find_map(|machine| {
+                machine.recipes.iter()
+                    .find(|recipe| &recipe.id(&machine.name) == id)
+                    .map(|recipe| (machine.name.clone(), recipe.clone()))
+            })
+    }
+
+    fn machine_recipes_where(&self, predicate: impl Fn(&GregtechRecipe) -> bool) -> Vec<(String, GregtechRecipe)> {
+        self.sources
+            .iter()
+            .flat_map(|source| &source.machines)
+            .flat_map(|machine| {
+                machine.recipes.iter()
+                    .filter(|recipe| predicate(recipe))
+                    .map(|recipe| (machine.name.clone(), recipe.clone()))
+            })
+            .collect()
+    }
+
+    pub fn search(&self, keyword: &str) -> Vec<(String, GregtechRecipe)> {
         let mut results = Vec::<(String, GregtechRecipe)>::new();
 
         const SEARCH_THRESHOLD: f64 = 0.7;
@@ -41,13 +113,13 @@ impl JsonFormat {
                     let mut found = false;
                     for item in &recipe.item_inputs {
                         if let Some(ref unlocalized_name) = item.unlocalized_name {
-                            if jaro_winkler(unlocalized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(unlocalized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
                         }
                         if let Some(ref localized_name) = item.localized_name {
-                            if jaro_winkler(localized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(localized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
@@ -55,13 +127,13 @@ impl JsonFormat {
                     }
                     for item in &recipe.item_outputs {
                         if let Some(ref unlocalized_name) = item.unlocalized_name {
-                            if jaro_winkler(unlocalized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(unlocalized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
                         }
                         if let Some(ref localized_name) = item.localized_name {
-                            if jaro_winkler(localized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(localized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
@@ -69,13 +141,13 @@ impl JsonFormat {
                     }
                     for fluid in &recipe.fluid_inputs {
                         if let Some(ref unlocalized_name) = fluid.unlocalized_name {
-                            if jaro_winkler(unlocalized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(unlocalized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
                         }
                         if let Some(ref localized_name) = fluid.localized_name {
-                            if jaro_winkler(localized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(localized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
@@ -83,13 +155,13 @@ impl JsonFormat {
                     }
                     for fluid in &recipe.fluid_outputs {
                         if let Some(ref unlocalized_name) = fluid.unlocalized_name {
-                            if jaro_winkler(unlocalized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(unlocalized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
                         }
                         if let Some(ref localized_name) = fluid.localized_name {
-                            if jaro_winkler(localized_name, &keyword) > SEARCH_THRESHOLD {
+                            if jaro_winkler(localized_name, keyword) > SEARCH_THRESHOLD {
                                 found = true;
                                 continue;
                             }
@@ -106,3 +178,17 @@ impl JsonFormat {
         results
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_machine_recipe_counts() {
+        let recipes = crate::load_file(&std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../recipes_small.json")));
+        let counts = recipes.machine_recipe_counts();
+        assert_eq!(counts.len(), 10);
+        assert!(counts.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        let machine_recipes = recipes.sources.iter().flat_map(|source| &source.machines).map(|machine| machine.recipes.len()).sum::<usize>();
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), machine_recipes);
+        assert!(machine_recipes > 0);
+    }
+}
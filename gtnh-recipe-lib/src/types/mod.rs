@@ -1,6 +1,10 @@
+pub mod bill_of_materials;
 pub mod gregtech_recipe;
 pub mod ingredients;
 pub mod gregtech_machine;
 pub mod json;
 pub mod recipe_stats;
 pub mod recipe_balance;
+pub mod recipe_id;
+pub mod production_chain;
+pub mod voltage_tier;
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::types::gregtech_recipe::GregtechRecipe;
+use crate::types::ingredients::IngredientKind;
+use crate::types::voltage_tier::VoltageTier;
+
+const MAX_ITERATIONS: usize = 1000;
+const EPSILON: f64 = 1e-9;
+
+/// A recipe taking part in a production chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChainStep {
+    pub machine_name: String,
+    pub recipe: GregtechRecipe,
+    /// Machine tier used for overclocking, defaults to the lowest tier able to run the recipe
+    #[serde(default)]
+    pub tier: Option<VoltageTier>,
+    /// Output this step is run for, defaults to the first output of the recipe.
+    /// Other outputs are only credited as byproducts.
+    #[serde(default)]
+    pub main_output: Option<String>,
+}
+
+impl ChainStep {
+    pub fn new(machine_name: &str, recipe: &GregtechRecipe) -> Self {
+        ChainStep {
+            machine_name: machine_name.to_string(),
+            recipe: recipe.clone(),
+            tier: None,
+            main_output: None,
+        }
+    }
+
+    pub fn tier(&self) -> VoltageTier {
+        self.tier.unwrap_or_else(|| VoltageTier::for_eut(self.recipe.eut))
+    }
+
+    /// Whether a machine of the step's tier provides the EU/t of the recipe.
+    pub fn tier_runs_recipe(&self) -> bool {
+        self.tier().voltage() >= self.recipe.eut
+    }
+
+    pub fn main_output(&self) -> Option<String> {
+        self.main_output.clone().or_else(|| {
+            self.recipe.item_outputs.first().map(|item| item.get_name())
+                .or_else(|| self.recipe.fluid_outputs.first().map(|fluid| fluid.get_name()))
+        })
+    }
+
+    /// Amount of `name` produced by a single craft.
+    fn output_amount(&self, name: &str) -> usize {
+        self.recipe.item_outputs.iter().filter(|item| item.get_name() == name).map(|item| item.amount).sum::<usize>()
+            + self.recipe.fluid_outputs.iter().filter(|fluid| fluid.get_name() == name).map(|fluid| fluid.amount).sum::<usize>()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IngredientRate {
+    pub name: String,
+    pub kind: IngredientKind,
+    /// Amount per second
+    pub rate: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StepSolution {
+    pub machine_name: String,
+    pub tier: VoltageTier,
+    /// EU/t of a single machine after overclocking
+    pub eut: usize,
+    /// Duration in ticks after overclocking
+    pub duration: usize,
+    pub crafts_per_second: f64,
+    pub machines: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChainSolution {
+    /// One entry per step of the chain, in the same order
+    pub steps: Vec<StepSolution>,
+    /// Average power draw of all machines in EU/t
+    pub power: f64,
+    /// Ingredients that have to be supplied from outside the chain
+    pub inputs: Vec<IngredientRate>,
+    /// Outputs of the chain, including the target
+    pub outputs: Vec<IngredientRate>,
+    /// False if the chain contains a loop that could not be balanced
+    pub converged: bool,
+}
+
+impl ChainSolution {
+    /// Outputs other than `target`.
+    pub fn byproducts(&self, target: &str) -> Vec<IngredientRate> {
+        self.outputs.iter().filter(|output| !output.name.eq_ignore_ascii_case(target)).cloned().collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProductionChain {
+    pub steps: Vec<ChainStep>,
+}
+
+impl ProductionChain {
+    pub fn new(steps: Vec<ChainStep>) -> Self {
+        ProductionChain { steps }
+    }
+
+    /// Calculates how often every step has to run to produce `rate` of `target` per second.
+    ///
+    /// Deficits of an ingredient are filled by the step having it as main output, byproducts of
+    /// other steps are used before that. Ingredients without such a step become chain inputs.
+    pub fn solve(&self, target: &str, rate: f64) -> ChainSolution {
+        let target = self.resolve_name(target);
+        let target_producer = self.producer_of(&target);
+        let mut crafts = vec![0.0; self.steps.len()];
+        let mut converged = false;
+
+        for _ in 0..MAX_ITERATIONS {
+            let deficit = self.balance(&crafts).into_iter().find_map(|(name, (_, amount))| {
+                let (required, producer) = if name == target {
+                    (rate, target_producer)
+                } else {
+                    (0.0, self.main_producer_of(&name))
+                };
+                match producer {
+                    Some(index) if required - amount > EPSILON * rate.max(1.0) => Some((index, name, required - amount)),
+                    _ => None,
+                }
+            });
+            match deficit {
+                Some((index, name, missing)) => {
+                    crafts[index] += missing / self.steps[index].output_amount(&name) as f64;
+                }
+                None => {
+                    converged = true;
+                    break;
+                }
+            }
+        }
+
+        let steps = self.steps.iter().zip(&crafts).map(|(step, crafts_per_second)| {
+            let tier = step.tier();
+            let (eut, duration) = tier.overclock(step.recipe.eut, step.recipe.duration);
+            StepSolution {
+                machine_name: step.machine_name.clone(),
+                tier,
+                eut,
+                duration,
+                crafts_per_second: *crafts_per_second,
+                machines: crafts_per_second * duration as f64 / 20.0,
+            }
+        }).collect::<Vec<StepSolution>>();
+        let power = steps.iter().map(|step| step.machines * step.eut as f64).sum();
+
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for (name, (kind, amount)) in self.balance(&crafts) {
+            if amount < -EPSILON {
+                inputs.push(IngredientRate { name, kind, rate: -amount });
+            } else if amount > EPSILON {
+                outputs.push(IngredientRate { name, kind, rate: amount });
+            }
+        }
+
+        ChainSolution { steps, power, inputs, outputs, converged }
+    }
+
+    /// Net production per second of every ingredient in the chain.
+    fn balance(&self, crafts: &[f64]) -> BTreeMap<String, (IngredientKind, f64)> {
+        let mut balance = BTreeMap::new();
+        for (step, crafts_per_second) in self.steps.iter().zip(crafts) {
+            let recipe = &step.recipe;
+            for item in &recipe.item_inputs {
+                balance.entry(item.get_name()).or_insert((IngredientKind::Item, 0.0)).1 -= item.amount as f64 * crafts_per_second;
+            }
+            for fluid in &recipe.fluid_inputs {
+                balance.entry(fluid.get_name()).or_insert((IngredientKind::Fluid, 0.0)).1 -= fluid.amount as f64 * crafts_per_second;
+            }
+            for item in &recipe.item_outputs {
+                balance.entry(item.get_name()).or_insert((IngredientKind::Item, 0.0)).1 += item.amount as f64 * crafts_per_second;
+            }
+            for fluid in &recipe.fluid_outputs {
+                balance.entry(fluid.get_name()).or_insert((IngredientKind::Fluid, 0.0)).1 += fluid.amount as f64 * crafts_per_second;
+            }
+        }
+        balance
+    }
+
+    /// Spelling of `name` used by the outputs of the chain, ignoring ASCII case.
+    fn resolve_name(&self, name: &str) -> String {
+        self.steps.iter()
+            .flat_map(|step| {
+                step.recipe.item_outputs.iter().map(|item| item.get_name())
+                    .chain(step.recipe.fluid_outputs.iter().map(|fluid| fluid.get_name()))
+            })
+            .find(|output| output.eq_ignore_ascii_case(name))
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn main_producer_of(&self, name: &str) -> Option<usize> {
+        self.steps.iter().position(|step| {
+            step.main_output().is_some_and(|output| output.eq_ignore_ascii_case(name)) && step.output_amount(name) > 0
+        })
+    }
+
+    /// Step producing `name`, preferring the one having it as main output.
+    fn producer_of(&self, name: &str) -> Option<usize> {
+        self.main_producer_of(name)
+            .or_else(|| self.steps.iter().position(|step| step.output_amount(name) > 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::RecipeBuilder;
+
+    /// NO + O2 -> NO2, 3 NO2 + H2O -> 2 HNO3 + NO
+    fn nitric_acid_chain() -> ProductionChain {
+        ProductionChain::new(vec![
+            ChainStep::new(
+                "Large Chemical Reactor",
+                &RecipeBuilder::new(30, 20)
+                    .fluid_inputs(&[("Nitrogen Dioxide", 3000), ("Water", 1000)])
+                    .fluid_outputs(&[("Nitric Acid", 2000), ("Nitric Oxide", 1000)])
+                    .build(),
+            ),
+            ChainStep::new(
+                "Large Chemical Reactor",
+                &RecipeBuilder::new(30, 10)
+                    .fluid_inputs(&[("Nitric Oxide", 1000), ("Oxygen Gas", 1000)])
+                    .fluid_outputs(&[("Nitrogen Dioxide", 1000)])
+                    .build(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = nitric_acid_chain().solve("nitric acid", 2000.0);
+        assert!(solution.converged);
+        assert!((solution.steps[0].crafts_per_second - 1.0).abs() < 1e-6);
+        assert!((solution.steps[1].crafts_per_second - 3.0).abs() < 1e-6);
+        assert!((solution.steps[0].machines - 1.0).abs() < 1e-6);
+        assert!((solution.steps[1].machines - 1.5).abs() < 1e-6);
+        assert!((solution.power - 75.0).abs() < 1e-6);
+
+        let input = |name: &str| solution.inputs.iter().find(|input| input.name == name).unwrap().rate;
+        assert!((input("Nitric Oxide") - 2000.0).abs() < 1e-6);
+        assert!((input("Oxygen Gas") - 3000.0).abs() < 1e-6);
+        assert!((input("Water") - 1000.0).abs() < 1e-6);
+        assert_eq!(solution.outputs.len(), 1);
+        assert!(solution.byproducts("Nitric Acid").is_empty());
+    }
+
+    #[test]
+    fn test_overclocked_step() {
+        let mut chain = nitric_acid_chain();
+        chain.steps[1].tier = Some(VoltageTier::MV);
+        let solution = chain.solve("Nitric Acid", 2000.0);
+        assert_eq!(solution.steps[1].eut, 120);
+        assert_eq!(solution.steps[1].duration, 5);
+        assert!((solution.steps[1].machines - 0.75).abs() < 1e-6);
+
+        assert!(chain.steps[1].tier_runs_recipe());
+        chain.steps[1].tier = Some(VoltageTier::ULV);
+        assert!(!chain.steps[1].tier_runs_recipe());
+    }
+}
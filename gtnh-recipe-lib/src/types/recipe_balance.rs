@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
 use crate::types::gregtech_recipe::GregtechRecipe;
 use crate::types::ingredients::item::Item;
 use crate::types::ingredients::fluid::Fluid;
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct RecipeBalance {
     pub input_items: Vec<Item>,
     pub input_fluids: Vec<Fluid>,
@@ -34,7 +36,7 @@ impl RecipeBalance {
         }
     }
 
-    fn combine_items(items1: &Vec<Item>, items2: &Vec<Item>) -> Vec<Item> {
+    fn combine_items(items1: &[Item], items2: &[Item]) -> Vec<Item> {
         let mut item_map: HashMap<String, usize> = HashMap::new();
 
         for item in items1.iter().chain(items2.iter()) {
@@ -51,7 +53,7 @@ impl RecipeBalance {
         }).collect()
     }
 
-    fn combine_fluids(fluids1: &Vec<Fluid>, fluids2: &Vec<Fluid>) -> Vec<Fluid> {
+    fn combine_fluids(fluids1: &[Fluid], fluids2: &[Fluid]) -> Vec<Fluid> {
         let mut fluid_map: HashMap<String, usize> = HashMap::new();
 
         for fluid in fluids1.iter().chain(fluids2.iter()) {
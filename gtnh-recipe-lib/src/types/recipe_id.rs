@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use crate::types::gregtech_recipe::GregtechRecipe;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a hash of `bytes`, unlike `DefaultHasher` its output is fixed across Rust versions and platforms.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Identifier of a machine recipe that stays the same across reloads and dumps.
+///
+/// It is derived from the machine, EU/t, duration and the unlocalized names and amounts of all
+/// ingredients. Ingredient order, localized names and whether the recipe is enabled are ignored,
+/// so the id survives language changes and most modpack updates.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct RecipeId(String);
+
+impl RecipeId {
+    pub fn new(machine_name: &str, recipe: &GregtechRecipe) -> Self {
+        RecipeId(format!("{:016x}", fnv1a(canonical_form(machine_name, recipe).as_bytes())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for RecipeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for RecipeId {
+    fn from(id: &str) -> Self {
+        RecipeId(id.to_string())
+    }
+}
+
+/// Sorted `name*amount` list of ingredients, using the unlocalized name where available.
+fn canonical_ingredients<'a>(ingredients: impl Iterator<Item = (&'a Option<String>, &'a Option<String>, usize)>) -> String {
+    let mut entries = ingredients
+        .map(|(unlocalized_name, localized_name, amount)| {
+            let name = unlocalized_name.as_deref().or(localized_name.as_deref()).unwrap_or_default();
+            format!("{}*{}", name, amount)
+        })
+        .collect::<Vec<String>>();
+    entries.sort();
+    entries.join(";")
+}
+
+fn canonical_form(machine_name: &str, recipe: &GregtechRecipe) -> String {
+    let item_inputs = canonical_ingredients(recipe.item_inputs.iter().map(|item| (&item.unlocalized_name, &item.localized_name, item.amount)));
+    let item_outputs = canonical_ingredients(recipe.item_outputs.iter().map(|item| (&item.unlocalized_name, &item.localized_name, item.amount)));
+    let fluid_inputs = canonical_ingredients(recipe.fluid_inputs.iter().map(|fluid| (&fluid.unlocalized_name, &fluid.localized_name, fluid.amount)));
+    let fluid_outputs = canonical_ingredients(recipe.fluid_outputs.iter().map(|fluid| (&fluid.unlocalized_name, &fluid.localized_name, fluid.amount)));
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        machine_name, recipe.eut, recipe.duration, item_inputs, fluid_inputs, item_outputs, fluid_outputs
+    )
+}
+
+impl GregtechRecipe {
+    pub fn id(&self, machine_name: &str) -> RecipeId {
+        RecipeId::new(machine_name, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::RecipeBuilder;
+    use crate::types::ingredients::item::Item;
+
+    fn item(unlocalized_name: &str, localized_name: &str, amount: usize) -> Item {
+        Item {
+            amount,
+            unlocalized_name: Some(unlocalized_name.to_string()),
+            localized_name: Some(localized_name.to_string()),
+        }
+    }
+
+    fn recipe(item_inputs: Vec<Item>, eut: usize) -> GregtechRecipe {
+        GregtechRecipe {
+            item_inputs,
+            item_outputs: vec![item("gt.metaitem.01.32402", "Empty Spray Can", 1)],
+            ..RecipeBuilder::new(eut, 800).build()
+        }
+    }
+
+    #[test]
+    fn test_recipe_id() {
+        let inputs = vec![item("item.redstone", "Redstone", 1), item("ic2.itemCellEmpty", "Empty Cell", 1)];
+        let id = recipe(inputs.clone(), 1).id("Assembler");
+        assert_eq!(id.as_str().len(), 16);
+        // Fixed value, ids must not change between versions of this library
+        assert_eq!(id, RecipeId::from("af423b43b0f7a8da"));
+
+        let mut reordered = inputs.clone();
+        reordered.reverse();
+        assert_eq!(recipe(reordered, 1).id("Assembler"), id);
+
+        let localized = vec![item("item.redstone", "Rotstein", 1), item("ic2.itemCellEmpty", "Leere Zelle", 1)];
+        let mut disabled = recipe(localized, 1);
+        disabled.enabled = false;
+        assert_eq!(disabled.id("Assembler"), id);
+
+        assert_ne!(recipe(inputs.clone(), 2).id("Assembler"), id);
+        assert_ne!(recipe(inputs, 1).id("Mixer"), id);
+    }
+}
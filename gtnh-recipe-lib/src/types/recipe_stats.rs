@@ -3,7 +3,6 @@ use crate::types::ingredients::fluid::Fluid;
 use crate::types::ingredients::item::Item;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use itertools::Itertools;
 
 pub struct RecipeStats {
     pub total_input_items: Vec<Item>,
@@ -14,51 +13,46 @@ pub struct RecipeStats {
 
 impl RecipeStats {
     pub fn new(input: Vec<GregtechRecipe>) -> Self {
-        let mut iter = input.iter();
-        let first = iter.next().unwrap();
-        let second = iter.next().unwrap();
         RecipeStats {
-            total_input_items: Self::combine_items(&input),
-            total_input_fluids: Self::combine_fluids(&first.fluid_inputs, &second.fluid_inputs),
-            total_output_items: Self::combine_items(&input),
-            total_output_fluids: Self::combine_fluids(&first.fluid_outputs, &second.fluid_outputs),
+            total_input_items: Self::combine_items(input.iter().flat_map(|recipe| &recipe.item_inputs)),
+            total_input_fluids: Self::combine_fluids(input.iter().flat_map(|recipe| &recipe.fluid_inputs)),
+            total_output_items: Self::combine_items(input.iter().flat_map(|recipe| &recipe.item_outputs)),
+            total_output_fluids: Self::combine_fluids(input.iter().flat_map(|recipe| &recipe.fluid_outputs)),
         }
     }
 
-    fn combine_items(recipes: &Vec<GregtechRecipe>) -> Vec<Item> {
-        let mut item_map: HashMap<String, Item> = HashMap::new();
-
-        for recipe in recipes {
-            for item in &recipe.item_inputs {
-                let name = item.get_name();
-                item_map
-                    .entry(name)
-                    .or_insert(Item::new(&item.unlocalized_name, &item.localized_name))
-                    .amount += item.amount;
-            }
+    fn combine_items<'a>(input: impl Iterator<Item = &'a Item>) -> Vec<Item> {
+        let mut item_indices: HashMap<String, usize> = HashMap::new();
+        let mut items: Vec<Item> = Vec::new();
+
+        for item in input {
+            let name = item.get_name();
+            let index = *item_indices.entry(name).or_insert_with(|| {
+                items.push(Item::new(&item.unlocalized_name, &item.localized_name));
+                items.len() - 1
+            });
+            items[index].amount += item.amount;
         }
-        item_map.values().cloned().collect()
+        items
     }
 
-    fn combine_fluids(fluids1: &Vec<Fluid>, fluids2: &Vec<Fluid>) -> Vec<Fluid> {
-        let mut fluid_map: HashMap<String, usize> = HashMap::new();
-
-        for fluid in fluids1.iter().chain(fluids2.iter()) {
-            let name = fluid
-                .unlocalized_name
-                .clone()
-                .unwrap_or_else(|| fluid.localized_name.clone().unwrap());
-            *fluid_map.entry(name).or_insert(0) += fluid.amount;
+    fn combine_fluids<'a>(input: impl Iterator<Item = &'a Fluid>) -> Vec<Fluid> {
+        let mut fluid_indices: HashMap<String, usize> = HashMap::new();
+        let mut fluids: Vec<Fluid> = Vec::new();
+
+        for fluid in input {
+            let name = fluid.get_name();
+            let index = *fluid_indices.entry(name).or_insert_with(|| {
+                fluids.push(Fluid {
+                    amount: 0,
+                    unlocalized_name: fluid.unlocalized_name.clone(),
+                    localized_name: fluid.localized_name.clone(),
+                });
+                fluids.len() - 1
+            });
+            fluids[index].amount += fluid.amount;
         }
-
-        fluid_map
-            .into_iter()
-            .map(|(name, amount)| Fluid {
-                amount,
-                unlocalized_name: Some(name.clone()),
-                localized_name: None,
-            })
-            .collect()
+        fluids
     }
 }
 
@@ -185,9 +179,7 @@ mod tests {
             fluid_outputs: vec![],
         };
 
-        let mut both_recipes = Vec::<GregtechRecipe>::new();
-        both_recipes.push(first_recipe);
-        both_recipes.push(second_recipe);
+        let both_recipes = vec![first_recipe, second_recipe];
         let stats = RecipeStats::new(both_recipes);
 
 
@@ -215,5 +207,20 @@ mod tests {
         ];
 
         assert_eq!(stats.total_input_items, total_input_items);
+
+        let total_output_items = [
+            Item {
+                amount: 6,
+                unlocalized_name: Some("item3".to_string()),
+                localized_name: Some("Item 3".to_string()),
+            },
+            Item {
+                amount: 4,
+                unlocalized_name: Some("item5".to_string()),
+                localized_name: Some("Item 5".to_string()),
+            },
+        ];
+
+        assert_eq!(stats.total_output_items, total_output_items);
     }
 }
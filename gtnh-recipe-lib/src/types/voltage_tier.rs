@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VoltageTier {
+    ULV,
+    LV,
+    MV,
+    HV,
+    EV,
+    IV,
+    LuV,
+    ZPM,
+    UV,
+    UHV,
+    UEV,
+    UIV,
+    UMV,
+    UXV,
+    MAX,
+}
+
+impl VoltageTier {
+    pub const ALL: [VoltageTier; 15] = [
+        VoltageTier::ULV,
+        VoltageTier::LV,
+        VoltageTier::MV,
+        VoltageTier::HV,
+        VoltageTier::EV,
+        VoltageTier::IV,
+        VoltageTier::LuV,
+        VoltageTier::ZPM,
+        VoltageTier::UV,
+        VoltageTier::UHV,
+        VoltageTier::UEV,
+        VoltageTier::UIV,
+        VoltageTier::UMV,
+        VoltageTier::UXV,
+        VoltageTier::MAX,
+    ];
+
+    /// Maximum EU/t a single amp of this tier provides (8 EU/t for ULV, times 4 per tier).
+    pub fn voltage(&self) -> usize {
+        8 << (2 * *self as usize)
+    }
+
+    /// Lowest tier able to run a recipe with the given EU/t.
+    pub fn for_eut(eut: usize) -> VoltageTier {
+        *Self::ALL
+            .iter()
+            .find(|tier| tier.voltage() >= eut)
+            .unwrap_or(&VoltageTier::MAX)
+    }
+
+    /// EU/t and duration of a recipe when run on a machine of this tier.
+    ///
+    /// Every overclock quadruples the power draw and halves the duration, down to a single tick.
+    pub fn overclock(&self, eut: usize, duration: usize) -> (usize, usize) {
+        let mut eut = eut;
+        let mut duration = duration;
+        while eut > 0 && eut * 4 <= self.voltage() && duration > 1 {
+            eut *= 4;
+            duration /= 2;
+        }
+        (eut, duration)
+    }
+
+    pub fn from_name(name: &str) -> Option<VoltageTier> {
+        Self::ALL
+            .iter()
+            .find(|tier| tier.to_string().eq_ignore_ascii_case(name))
+            .copied()
+    }
+}
+
+impl Display for VoltageTier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_eut() {
+        assert_eq!(VoltageTier::for_eut(0), VoltageTier::ULV);
+        assert_eq!(VoltageTier::for_eut(30), VoltageTier::LV);
+        assert_eq!(VoltageTier::for_eut(32), VoltageTier::LV);
+        assert_eq!(VoltageTier::for_eut(480), VoltageTier::HV);
+        assert_eq!(VoltageTier::for_eut(7680), VoltageTier::IV);
+        assert_eq!(VoltageTier::LuV.voltage(), 32768);
+        assert_eq!(VoltageTier::from_name("luv"), Some(VoltageTier::LuV));
+    }
+
+    #[test]
+    fn test_overclock() {
+        assert_eq!(VoltageTier::LV.overclock(30, 200), (30, 200));
+        assert_eq!(VoltageTier::MV.overclock(30, 200), (120, 100));
+        assert_eq!(VoltageTier::EV.overclock(30, 200), (1920, 25));
+        assert_eq!(VoltageTier::IV.overclock(480, 2), (1920, 1));
+    }
+}
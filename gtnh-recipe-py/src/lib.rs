@@ -0,0 +1,334 @@
+// pyo3 0.22 macro expansion trips this lint on every `PyResult` return
+#![allow(clippy::useless_conversion)]
+
+use std::path::PathBuf;
+use gtnh_recipe_lib::load::try_load_bytes;
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::ingredients::fluid::Fluid;
+use gtnh_recipe_lib::types::ingredients::item::Item;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use gtnh_recipe_lib::types::recipe_balance::RecipeBalance;
+use gtnh_recipe_lib::types::recipe_id::RecipeId;
+use gtnh_recipe_lib::types::recipe_stats::RecipeStats;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+#[pyclass(name = "Item", module = "gtnh_recipe", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Hash)]
+pub struct PyItem(Item);
+
+#[pymethods]
+impl PyItem {
+    #[new]
+    #[pyo3(signature = (amount, unlocalized_name=None, localized_name=None))]
+    fn new(amount: usize, unlocalized_name: Option<String>, localized_name: Option<String>) -> Self {
+        PyItem(Item { amount, unlocalized_name, localized_name })
+    }
+
+    #[getter]
+    fn amount(&self) -> usize {
+        self.0.amount
+    }
+
+    #[getter]
+    fn unlocalized_name(&self) -> Option<String> {
+        self.0.unlocalized_name.clone()
+    }
+
+    #[getter]
+    fn localized_name(&self) -> Option<String> {
+        self.0.localized_name.clone()
+    }
+
+    /// Localized name, falling back to the unlocalized one
+    #[getter]
+    fn name(&self) -> String {
+        self.0.get_name()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Item({}, {:?}, {:?})", self.0.amount, self.0.unlocalized_name, self.0.localized_name)
+    }
+}
+
+#[pyclass(name = "Fluid", module = "gtnh_recipe", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Hash)]
+pub struct PyFluid(Fluid);
+
+#[pymethods]
+impl PyFluid {
+    #[new]
+    #[pyo3(signature = (amount, unlocalized_name=None, localized_name=None))]
+    fn new(amount: usize, unlocalized_name: Option<String>, localized_name: Option<String>) -> Self {
+        PyFluid(Fluid { amount, unlocalized_name, localized_name })
+    }
+
+    #[getter]
+    fn amount(&self) -> usize {
+        self.0.amount
+    }
+
+    #[getter]
+    fn unlocalized_name(&self) -> Option<String> {
+        self.0.unlocalized_name.clone()
+    }
+
+    #[getter]
+    fn localized_name(&self) -> Option<String> {
+        self.0.localized_name.clone()
+    }
+
+    /// Localized name, falling back to the unlocalized one
+    #[getter]
+    fn name(&self) -> String {
+        self.0.get_name()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Fluid({}, {:?}, {:?})", self.0.amount, self.0.unlocalized_name, self.0.localized_name)
+    }
+}
+
+fn py_items(items: &[Item]) -> Vec<PyItem> {
+    items.iter().cloned().map(PyItem).collect()
+}
+
+fn py_fluids(fluids: &[Fluid]) -> Vec<PyFluid> {
+    fluids.iter().cloned().map(PyFluid).collect()
+}
+
+/// A machine recipe together with the name of its machine
+#[pyclass(name = "Recipe", module = "gtnh_recipe", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Hash)]
+pub struct PyRecipe {
+    machine_name: String,
+    recipe: GregtechRecipe,
+}
+
+#[pymethods]
+impl PyRecipe {
+    /// Stable id, the same across reloads and dumps
+    #[getter]
+    fn id(&self) -> String {
+        self.recipe.id(&self.machine_name).to_string()
+    }
+
+    #[getter]
+    fn machine_name(&self) -> String {
+        self.machine_name.clone()
+    }
+
+    #[getter]
+    fn enabled(&self) -> bool {
+        self.recipe.enabled
+    }
+
+    /// Duration in ticks
+    #[getter]
+    fn duration(&self) -> usize {
+        self.recipe.duration
+    }
+
+    #[getter]
+    fn eut(&self) -> usize {
+        self.recipe.eut
+    }
+
+    #[getter]
+    fn item_inputs(&self) -> Vec<PyItem> {
+        py_items(&self.recipe.item_inputs)
+    }
+
+    #[getter]
+    fn item_outputs(&self) -> Vec<PyItem> {
+        py_items(&self.recipe.item_outputs)
+    }
+
+    #[getter]
+    fn fluid_inputs(&self) -> Vec<PyFluid> {
+        py_fluids(&self.recipe.fluid_inputs)
+    }
+
+    #[getter]
+    fn fluid_outputs(&self) -> Vec<PyFluid> {
+        py_fluids(&self.recipe.fluid_outputs)
+    }
+
+    fn produces(&self, name: &str) -> bool {
+        self.recipe.produces(name)
+    }
+
+    fn uses(&self, name: &str) -> bool {
+        self.recipe.uses(name)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.recipe)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Recipe({:?}, {:?})", self.machine_name, format!("{}", self.recipe))
+    }
+}
+
+fn py_recipes(recipes: Vec<(String, GregtechRecipe)>) -> Vec<PyRecipe> {
+    recipes
+        .into_iter()
+        .map(|(machine_name, recipe)| PyRecipe { machine_name, recipe })
+        .collect()
+}
+
+/// A loaded recipes.json dump
+#[pyclass(name = "RecipeDatabase", module = "gtnh_recipe", frozen)]
+pub struct PyRecipeDatabase(JsonFormat);
+
+#[pymethods]
+impl PyRecipeDatabase {
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Reads a JSON dump or a binary cache written by `gtnh-recipe-calculator cache`
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        try_load_bytes(bytes)
+            .map(PyRecipeDatabase)
+            .map_err(|err| PyValueError::new_err(format!("invalid recipe dump: {}", err)))
+    }
+
+    /// Number of recipes of all sources
+    #[getter]
+    fn recipe_count(&self) -> usize {
+        self.0.get_recipe_count()
+    }
+
+    /// Machine names of all sources, sorted and each once like the server's `/machines`
+    fn machines(&self) -> Vec<String> {
+        self.0.machine_recipe_counts().into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn recipes_of_machine(&self, machine_name: &str) -> Vec<PyRecipe> {
+        py_recipes(self.0.recipes_of_machine(machine_name))
+    }
+
+    /// Fuzzy search over ingredient names, like the viewer
+    #[pyo3(signature = (keyword, limit=None))]
+    fn search(&self, keyword: &str, limit: Option<usize>) -> Vec<PyRecipe> {
+        let mut results = self.0.search(keyword);
+        if let Some(limit) = limit {
+            results.truncate(limit);
+        }
+        py_recipes(results)
+    }
+
+    fn recipe_by_id(&self, id: &str) -> Option<PyRecipe> {
+        self.0
+            .recipe_by_id(&RecipeId::from(id))
+            .map(|(machine_name, recipe)| PyRecipe { machine_name, recipe })
+    }
+
+    /// Recipes with an item or fluid output called `name`
+    fn recipes_producing(&self, name: &str) -> Vec<PyRecipe> {
+        py_recipes(self.0.recipes_producing(name))
+    }
+
+    /// Recipes with an item or fluid input called `name`
+    fn recipes_using(&self, name: &str) -> Vec<PyRecipe> {
+        py_recipes(self.0.recipes_using(name))
+    }
+}
+
+/// Net inputs and outputs of two recipes, where outputs of the first one feed the second one
+#[pyclass(name = "RecipeBalance", module = "gtnh_recipe", frozen)]
+pub struct PyRecipeBalance(RecipeBalance);
+
+#[pymethods]
+impl PyRecipeBalance {
+    #[new]
+    fn new(first: &PyRecipe, second: &PyRecipe) -> PyResult<Self> {
+        if !first.recipe.has_ingredient_names() || !second.recipe.has_ingredient_names() {
+            return Err(PyValueError::new_err("every ingredient needs an unlocalized or localized name"));
+        }
+        Ok(PyRecipeBalance(RecipeBalance::new(&first.recipe, &second.recipe)))
+    }
+
+    #[getter]
+    fn input_items(&self) -> Vec<PyItem> {
+        py_items(&self.0.input_items)
+    }
+
+    #[getter]
+    fn input_fluids(&self) -> Vec<PyFluid> {
+        py_fluids(&self.0.input_fluids)
+    }
+
+    #[getter]
+    fn output_items(&self) -> Vec<PyItem> {
+        py_items(&self.0.output_items)
+    }
+
+    #[getter]
+    fn output_fluids(&self) -> Vec<PyFluid> {
+        py_fluids(&self.0.output_fluids)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.0)
+    }
+}
+
+/// Summed inputs and outputs of a list of recipes
+#[pyclass(name = "RecipeStats", module = "gtnh_recipe", frozen)]
+pub struct PyRecipeStats(RecipeStats);
+
+#[pymethods]
+impl PyRecipeStats {
+    #[new]
+    fn new(recipes: Vec<PyRef<'_, PyRecipe>>) -> Self {
+        PyRecipeStats(RecipeStats::new(recipes.iter().map(|recipe| recipe.recipe.clone()).collect()))
+    }
+
+    #[getter]
+    fn total_input_items(&self) -> Vec<PyItem> {
+        py_items(&self.0.total_input_items)
+    }
+
+    #[getter]
+    fn total_input_fluids(&self) -> Vec<PyFluid> {
+        py_fluids(&self.0.total_input_fluids)
+    }
+
+    #[getter]
+    fn total_output_items(&self) -> Vec<PyItem> {
+        py_items(&self.0.total_output_items)
+    }
+
+    #[getter]
+    fn total_output_fluids(&self) -> Vec<PyFluid> {
+        py_fluids(&self.0.total_output_fluids)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.0)
+    }
+}
+
+#[pymodule]
+fn gtnh_recipe(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyItem>()?;
+    m.add_class::<PyFluid>()?;
+    m.add_class::<PyRecipe>()?;
+    m.add_class::<PyRecipeDatabase>()?;
+    m.add_class::<PyRecipeBalance>()?;
+    m.add_class::<PyRecipeStats>()?;
+    Ok(())
+}
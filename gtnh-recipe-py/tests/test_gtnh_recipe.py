@@ -0,0 +1,65 @@
+import json
+import pathlib
+import unittest
+
+import gtnh_recipe
+
+FIXTURE = pathlib.Path(__file__).parents[2] / "recipes_small.json"
+
+
+class RecipeDatabaseTest(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = gtnh_recipe.RecipeDatabase.load(FIXTURE)
+
+    def test_load(self):
+        self.assertEqual(self.db.recipe_count, 87)
+        self.assertIn("Electrolyzer", self.db.machines())
+        self.assertEqual(self.db.machines(), sorted(set(self.db.machines())))
+        with self.assertRaises(ValueError):
+            gtnh_recipe.RecipeDatabase.from_bytes(b"{}")
+
+    def test_binary_cache(self):
+        # Header and the MessagePack map {"sources": []}
+        empty = gtnh_recipe.RecipeDatabase.from_bytes(b"GTNHRCP\x01\x81\xa7sources\x90")
+        self.assertEqual(empty.recipe_count, 0)
+
+    def test_machines_merged(self):
+        source = {"type": "gregtech", "machines": [{"n": "Mixer", "recs": []}, {"n": "Assembler", "recs": []}]}
+        db = gtnh_recipe.RecipeDatabase.from_bytes(json.dumps({"sources": [source, source]}).encode())
+        self.assertEqual(db.machines(), ["Assembler", "Mixer"])
+
+    def test_search(self):
+        results = self.db.search("Redstone", limit=2)
+        self.assertEqual(len(results), 2)
+        self.assertIsInstance(results[0], gtnh_recipe.Recipe)
+
+    def test_reverse_lookup(self):
+        [recipe] = self.db.recipes_producing("empty spray can")
+        self.assertEqual(recipe.machine_name, "Assembler")
+        self.assertEqual(recipe.item_outputs[0].name, "Empty Spray Can")
+        self.assertTrue(all(r.uses("Empty Cell") for r in self.db.recipes_using("ic2.itemCellEmpty")))
+
+    def test_recipe_id(self):
+        [recipe] = self.db.recipes_producing("Empty Spray Can")
+        self.assertEqual(self.db.recipe_by_id(recipe.id), recipe)
+        self.assertIsNone(self.db.recipe_by_id("0000000000000000"))
+
+    def test_balance_and_stats(self):
+        first, second = self.db.recipes_producing("Helium-3")[:2]
+        balance = gtnh_recipe.RecipeBalance(first, second)
+        self.assertTrue(balance.output_fluids)
+
+        unnamed = {"en": True, "dur": 20, "eut": 30, "iI": [], "iO": [], "fI": [], "fO": [{"a": 1000}]}
+        dump = {"sources": [{"type": "gregtech", "machines": [{"n": "Mixer", "recs": [unnamed]}]}]}
+        [unnamed_recipe] = gtnh_recipe.RecipeDatabase.from_bytes(json.dumps(dump).encode()).recipes_of_machine("Mixer")
+        with self.assertRaises(ValueError):
+            gtnh_recipe.RecipeBalance(first, unnamed_recipe)
+
+        stats = gtnh_recipe.RecipeStats([first, second])
+        helium = [f for f in stats.total_output_fluids if f.name == "Helium-3"]
+        self.assertEqual(helium[0].amount, first.fluid_outputs[0].amount + second.fluid_outputs[0].amount)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use gtnh_recipe_lib::types::production_chain::{ChainSolution, ChainStep, ProductionChain};
+use gtnh_recipe_lib::types::recipe_balance::RecipeBalance;
+use gtnh_recipe_lib::types::recipe_id::RecipeId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MachineRecipe {
+    /// Stable id of the recipe, see `RecipeId`
+    pub id: RecipeId,
+    pub machine_name: String,
+    pub recipe: GregtechRecipe,
+}
+
+impl MachineRecipe {
+    fn new(machine_name: String, recipe: GregtechRecipe) -> Self {
+        MachineRecipe { id: recipe.id(&machine_name), machine_name, recipe }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MachineSummary {
+    pub name: String,
+    pub recipe_count: usize,
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BalanceRequest {
+    pub first: GregtechRecipe,
+    pub second: GregtechRecipe,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlanRequest {
+    pub steps: Vec<ChainStep>,
+    pub target: String,
+    /// Requested amount of `target` per second
+    pub rate: f64,
+}
+
+/// Builds the API routes over a loaded recipe dump.
+pub fn router(recipes: Arc<JsonFormat>) -> Router {
+    Router::new()
+        .route("/search", get(search))
+        .route("/recipe/:id", get(recipe_by_id))
+        .route("/recipes/:item", get(recipes_producing))
+        .route("/uses/:item", get(recipes_using))
+        .route("/machines", get(machines))
+        .route("/balance", post(balance))
+        .route("/plan", post(plan))
+        .with_state(recipes)
+}
+
+fn machine_recipes(recipes: Vec<(String, GregtechRecipe)>) -> Json<Vec<MachineRecipe>> {
+    Json(
+        recipes
+            .into_iter()
+            .map(|(machine_name, recipe)| MachineRecipe::new(machine_name, recipe))
+            .collect(),
+    )
+}
+
+async fn search(State(recipes): State<Arc<JsonFormat>>, Query(query): Query<SearchQuery>) -> Json<Vec<MachineRecipe>> {
+    let mut results = recipes.search(&query.q);
+    if let Some(limit) = query.limit {
+        results.truncate(limit);
+    }
+    machine_recipes(results)
+}
+
+async fn recipe_by_id(State(recipes): State<Arc<JsonFormat>>, Path(id): Path<String>) -> Result<Json<MachineRecipe>, StatusCode> {
+    recipes
+        .recipe_by_id(&RecipeId::from(id.as_str()))
+        .map(|(machine_name, recipe)| Json(MachineRecipe::new(machine_name, recipe)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn recipes_producing(State(recipes): State<Arc<JsonFormat>>, Path(item): Path<String>) -> Json<Vec<MachineRecipe>> {
+    machine_recipes(recipes.recipes_producing(&item))
+}
+
+async fn recipes_using(State(recipes): State<Arc<JsonFormat>>, Path(item): Path<String>) -> Json<Vec<MachineRecipe>> {
+    machine_recipes(recipes.recipes_using(&item))
+}
+
+async fn machines(State(recipes): State<Arc<JsonFormat>>) -> Json<Vec<MachineSummary>> {
+    Json(
+        recipes
+            .machine_recipe_counts()
+            .into_iter()
+            .map(|(name, recipe_count)| MachineSummary { name, recipe_count })
+            .collect(),
+    )
+}
+
+async fn balance(Json(request): Json<BalanceRequest>) -> Result<Json<RecipeBalance>, StatusCode> {
+    if !request.first.has_ingredient_names() || !request.second.has_ingredient_names() {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+    Ok(Json(RecipeBalance::new(&request.first, &request.second)))
+}
+
+async fn plan(Json(request): Json<PlanRequest>) -> Result<Json<ChainSolution>, StatusCode> {
+    if !request.steps.iter().all(ChainStep::tier_runs_recipe) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Json(ProductionChain::new(request.steps).solve(&request.target, request.rate)))
+}
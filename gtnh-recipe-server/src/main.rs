@@ -0,0 +1,28 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(version, about = "JSON API over a GTNH recipe dump")]
+struct Cli {
+    /// Recipe dump exported by RecEx
+    #[arg(short, long, default_value = "./recipes.json")]
+    file: PathBuf,
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    address: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let recipes = gtnh_recipe_lib::load_file(&cli.file);
+    println!("Loaded {} recipes from {}", recipes.get_recipe_count(), cli.file.display());
+
+    let listener = tokio::net::TcpListener::bind(cli.address).await.expect("unable to bind address");
+    println!("Listening on http://{}", cli.address);
+    axum::serve(listener, gtnh_recipe_server::router(Arc::new(recipes)))
+        .await
+        .expect("server error");
+}
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use axum::body::Body;
+use axum::response::Response;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use gtnh_recipe_lib::types::production_chain::{ChainSolution, ChainStep};
+use gtnh_recipe_lib::types::voltage_tier::VoltageTier;
+use gtnh_recipe_lib::types::recipe_balance::RecipeBalance;
+use gtnh_recipe_server::{BalanceRequest, MachineRecipe, MachineSummary, PlanRequest};
+use http_body_util::BodyExt;
+use serde::de::DeserializeOwned;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    let fixture = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../recipes_small.json"));
+    gtnh_recipe_server::router(Arc::new(gtnh_recipe_lib::load_file(&fixture)))
+}
+
+async fn get<T: DeserializeOwned>(uri: &str) -> T {
+    let response = app()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn post_response(uri: &str, body: String) -> Response {
+    app()
+        .oneshot(
+            Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn post<T: DeserializeOwned>(uri: &str, body: String) -> T {
+    let response = post_response(uri, body).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_machines() {
+    let machines: Vec<MachineSummary> = get("/machines").await;
+    assert_eq!(machines.len(), 10);
+    assert!(machines.windows(2).all(|pair| pair[0].name < pair[1].name));
+    assert!(machines.contains(&MachineSummary { name: "Assembler".to_string(), recipe_count: 10 }));
+}
+
+#[tokio::test]
+async fn test_search() {
+    let results: Vec<MachineRecipe> = get("/search?q=Redstone&limit=3").await;
+    assert_eq!(results.len(), 3);
+
+    let response = app()
+        .oneshot(Request::get("/search").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_recipes_and_uses() {
+    let producing: Vec<MachineRecipe> = get("/recipes/Empty%20Spray%20Can").await;
+    assert_eq!(producing.len(), 1);
+    assert_eq!(producing[0].machine_name, "Assembler");
+
+    let using: Vec<MachineRecipe> = get("/uses/ic2.itemCellEmpty").await;
+    assert!(!using.is_empty());
+    assert!(using.iter().all(|result| result.recipe.uses("Empty Cell")));
+
+    let unknown: Vec<MachineRecipe> = get("/uses/Unobtainium").await;
+    assert!(unknown.is_empty());
+}
+
+#[tokio::test]
+async fn test_recipe_by_id() {
+    let producing: Vec<MachineRecipe> = get("/recipes/Empty%20Spray%20Can").await;
+    let id = &producing[0].id;
+    assert_eq!(id, &producing[0].recipe.id("Assembler"));
+
+    let recipe: MachineRecipe = get(&format!("/recipe/{}", id)).await;
+    assert_eq!(recipe, producing.into_iter().next().unwrap());
+
+    let response = app()
+        .oneshot(Request::get("/recipe/0000000000000000").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_balance_and_plan() {
+    let electrolysis: Vec<MachineRecipe> = get("/recipes/Helium-3").await;
+    let first = electrolysis[0].recipe.clone();
+    let second = electrolysis[1].recipe.clone();
+
+    let balance: RecipeBalance = post(
+        "/balance",
+        serde_json::to_string(&BalanceRequest { first: first.clone(), second }).unwrap(),
+    )
+    .await;
+    assert!(!balance.output_fluids.is_empty());
+
+    let request = PlanRequest {
+        steps: vec![ChainStep::new(&electrolysis[0].machine_name, &first)],
+        target: "Helium-3".to_string(),
+        rate: first.fluid_outputs[0].amount as f64,
+    };
+    let solution: ChainSolution = post("/plan", serde_json::to_string(&request).unwrap()).await;
+    assert!(solution.converged);
+    assert!((solution.steps[0].crafts_per_second - 1.0).abs() < 1e-6);
+    assert!(!solution.inputs.is_empty());
+}
+
+#[tokio::test]
+async fn test_balance_and_plan_reject_invalid_input() {
+    let electrolysis: Vec<MachineRecipe> = get("/recipes/Helium-3").await;
+    let first = electrolysis[0].recipe.clone();
+
+    let mut unnamed = first.clone();
+    unnamed.fluid_outputs[0].unlocalized_name = None;
+    unnamed.fluid_outputs[0].localized_name = None;
+    let response = post_response(
+        "/balance",
+        serde_json::to_string(&BalanceRequest { first: first.clone(), second: unnamed }).unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let mut step = ChainStep::new(&electrolysis[0].machine_name, &first);
+    step.tier = Some(VoltageTier::ULV);
+    assert!(first.eut > VoltageTier::ULV.voltage());
+    let request = PlanRequest { steps: vec![step], target: "Helium-3".to_string(), rate: 1.0 };
+    let response = post_response("/plan", serde_json::to_string(&request).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
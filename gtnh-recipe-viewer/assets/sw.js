@@ -1,9 +1,15 @@
-var cacheName = 'egui-template-pwa';
+// Bump the version to make installed apps download the new app shell
+var cacheName = 'gtnh-recipe-viewer-v1';
 var filesToCache = [
   './',
   './index.html',
   './gtnh-recipe-viewer.js',
   './gtnh-recipe-viewer_bg.wasm',
+  './assets/manifest.json',
+  './assets/icon-256.png',
+  './assets/icon-1024.png',
+  './assets/icon_ios_touch_192.png',
+  './assets/maskable_icon_x512.png',
 ];
 
 /* Start the service worker and cache all of the app's content */
@@ -15,10 +21,25 @@ self.addEventListener('install', function (e) {
   );
 });
 
-/* Serve cached content when offline */
+/* Drop the app shells of previous versions */
+self.addEventListener('activate', function (e) {
+  e.waitUntil(
+    caches.keys().then(function (names) {
+      return Promise.all(
+        names.filter(function (name) {
+          return name !== cacheName;
+        }).map(function (name) {
+          return caches.delete(name);
+        })
+      );
+    })
+  );
+});
+
+/* Serve cached content when offline, recipe dumps are kept in IndexedDB by the app itself */
 self.addEventListener('fetch', function (e) {
   e.respondWith(
-    caches.match(e.request).then(function (response) {
+    caches.match(e.request, { ignoreSearch: true }).then(function (response) {
       return response || fetch(e.request);
     })
   );
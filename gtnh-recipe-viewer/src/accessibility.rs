@@ -0,0 +1,232 @@
+use egui::{Color32, Id, Stroke, Visuals, WidgetInfo, WidgetType};
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use crate::i18n::{names, tr, tr_args, IngredientText};
+
+/// Temp data key of the palette of the current frame, for [`input_color`] and [`output_color`]
+const HIGHLIGHT_PALETTE: &str = "highlight_palette";
+
+/// Colours telling inputs and outputs apart
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HighlightPalette {
+    /// Red inputs and green outputs
+    #[default]
+    Standard,
+    /// Orange inputs and blue outputs from the Okabe-Ito palette, distinguishable with all common colour blindnesses
+    ColourBlindSafe,
+}
+
+impl HighlightPalette {
+    /// Input and output colour, darker ones on light backgrounds.
+    fn colors(self, dark_mode: bool) -> (Color32, Color32) {
+        match (self, dark_mode) {
+            (HighlightPalette::Standard, true) => (Color32::from_rgb(0xff, 0x8c, 0x78), Color32::from_rgb(0x82, 0xd2, 0x82)),
+            (HighlightPalette::Standard, false) => (Color32::from_rgb(0xb4, 0x28, 0x1e), Color32::from_rgb(0x1e, 0x78, 0x1e)),
+            (HighlightPalette::ColourBlindSafe, true) => (Color32::from_rgb(0xe6, 0x9f, 0x00), Color32::from_rgb(0x56, 0xb4, 0xe9)),
+            (HighlightPalette::ColourBlindSafe, false) => (Color32::from_rgb(0xd5, 0x5e, 0x00), Color32::from_rgb(0x00, 0x72, 0xb2)),
+        }
+    }
+}
+
+fn palette(ui: &egui::Ui) -> HighlightPalette {
+    ui.ctx().data(|data| data.get_temp(Id::new(HIGHLIGHT_PALETTE))).unwrap_or_default()
+}
+
+/// Colour of recipe inputs in the chosen palette
+pub fn input_color(ui: &egui::Ui) -> Color32 {
+    palette(ui).colors(ui.visuals().dark_mode).0
+}
+
+/// Colour of recipe outputs in the chosen palette
+pub fn output_color(ui: &egui::Ui) -> Color32 {
+    palette(ui).colors(ui.visuals().dark_mode).1
+}
+
+/// Pure black or white backgrounds, text and widget outlines in the opposite colour.
+fn high_contrast_visuals(dark_mode: bool) -> Visuals {
+    let (mut visuals, text, background) = if dark_mode {
+        (Visuals::dark(), Color32::WHITE, Color32::BLACK)
+    } else {
+        (Visuals::light(), Color32::BLACK, Color32::WHITE)
+    };
+    visuals.override_text_color = Some(text);
+    visuals.panel_fill = background;
+    visuals.window_fill = background;
+    visuals.extreme_bg_color = background;
+    visuals.faint_bg_color = if dark_mode { Color32::from_gray(40) } else { Color32::from_gray(215) };
+    visuals.window_stroke = Stroke::new(2.0, text);
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.fg_stroke = Stroke::new(widget.fg_stroke.width.max(1.5), text);
+        widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.0), text);
+    }
+    visuals.widgets.inactive.weak_bg_fill = background;
+    visuals.selection.bg_fill = if dark_mode { Color32::from_rgb(0x00, 0x5f, 0xd0) } else { Color32::from_rgb(0x00, 0x4a, 0xad) };
+    visuals.selection.stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.hyperlink_color = if dark_mode { Color32::from_rgb(0xff, 0xe0, 0x00) } else { Color32::from_rgb(0x00, 0x00, 0xc0) };
+    visuals.warn_fg_color = if dark_mode { Color32::YELLOW } else { Color32::from_rgb(0x8a, 0x4b, 0x00) };
+    visuals.error_fg_color = if dark_mode { Color32::from_rgb(0xff, 0x60, 0x60) } else { Color32::from_rgb(0xb0, 0x00, 0x00) };
+    visuals
+}
+
+/// Font scale, contrast and highlight colours, kept with the app settings
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub open: bool,
+    /// Factor applied to every text style
+    pub font_scale: f32,
+    pub high_contrast: bool,
+    pub palette: HighlightPalette,
+
+    /// Font scale and contrast last set on the context, to only touch the style on changes
+    #[serde(skip)]
+    applied: Option<(f32, bool)>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            font_scale: 1.0,
+            high_contrast: false,
+            palette: Default::default(),
+            applied: None,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Updates the style of `ctx`, call once per frame before drawing.
+    pub fn apply(&mut self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(Id::new(HIGHLIGHT_PALETTE), self.palette));
+
+        let dark_mode = ctx.style().visuals.dark_mode;
+        if self.high_contrast {
+            // Also follows the dark/light switch, which resets the visuals
+            let visuals = high_contrast_visuals(dark_mode);
+            if ctx.style().visuals != visuals {
+                ctx.set_visuals(visuals);
+            }
+        } else if self.applied.is_some_and(|(_, high_contrast)| high_contrast) {
+            ctx.set_visuals(if dark_mode { Visuals::dark() } else { Visuals::light() });
+        }
+
+        if self.applied.map(|(font_scale, _)| font_scale) != Some(self.font_scale) {
+            let font_scale = self.font_scale;
+            ctx.style_mut(|style| {
+                style.text_styles = egui::Style::default()
+                    .text_styles
+                    .into_iter()
+                    .map(|(text_style, font)| (text_style, egui::FontId::new(font.size * font_scale, font.family)))
+                    .collect();
+            });
+        }
+        self.applied = Some((self.font_scale, self.high_contrast));
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new(tr(ctx, "Display settings")).open(&mut open).show(ctx, |ui| {
+            egui::Grid::new("display_settings_grid").num_columns(2).show(ui, |ui| {
+                ui.label(tr(ui.ctx(), "Font size"));
+                ui.add(egui::Slider::new(&mut self.font_scale, 0.75..=2.5).step_by(0.05).custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+                ui.end_row();
+
+                ui.label(tr(ui.ctx(), "Theme"));
+                ui.horizontal(egui::widgets::global_dark_light_mode_buttons);
+                ui.end_row();
+
+                ui.label(tr(ui.ctx(), "Contrast"));
+                ui.checkbox(&mut self.high_contrast, tr(ui.ctx(), "High contrast"));
+                ui.end_row();
+
+                ui.label(tr(ui.ctx(), "Inputs and outputs"));
+                ui.vertical(|ui| {
+                    ui.radio_value(&mut self.palette, HighlightPalette::Standard, tr(ui.ctx(), "Red and green"));
+                    ui.radio_value(&mut self.palette, HighlightPalette::ColourBlindSafe, tr(ui.ctx(), "Orange and blue (colour-blind safe)"));
+                });
+                ui.end_row();
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.colored_label(input_color(ui), tr(ui.ctx(), "Inputs"));
+                ui.colored_label(output_color(ui), tr(ui.ctx(), "Outputs"));
+            });
+            if ui.button(tr(ui.ctx(), "Reset to defaults")).clicked() {
+                *self = DisplaySettings { open: true, applied: self.applied, ..Default::default() };
+            }
+        });
+        self.open = open;
+    }
+}
+
+/// What screen readers announce for a row of the results table.
+pub fn recipe_description(ctx: &egui::Context, row_index: usize, machine_name: &str, recipe: &GregtechRecipe) -> String {
+    let ingredients = |items: Vec<String>| if items.is_empty() { "—".to_string() } else { items.join(" + ") };
+    let names = names(ctx);
+    let inputs = recipe.item_inputs.iter().map(|item| item.text(names)).chain(recipe.fluid_inputs.iter().map(|fluid| fluid.text(names))).collect();
+    let outputs = recipe.item_outputs.iter().map(|item| item.text(names)).chain(recipe.fluid_outputs.iter().map(|fluid| fluid.text(names))).collect();
+    tr_args(
+        ctx,
+        "Result {}: {}, {} EU/t, {}s, inputs: {}, outputs: {}",
+        &[&row_index, &machine_name, &recipe.eut, &(recipe.duration as f64 / 20.0), &ingredients(inputs), &ingredients(outputs)],
+    )
+}
+
+/// Announces `response` as a selectable entry named `label`, e.g. a table row.
+pub fn selectable_info(response: &egui::Response, selected: bool, label: &str) {
+    response.widget_info(|| WidgetInfo::selected(WidgetType::SelectableLabel, true, selected, label));
+}
+
+/// Announces `response` as a button named `label`, e.g. a clickable text.
+pub fn button_info(response: &egui::Response, label: &str) {
+    response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, true, label));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_accesskit_labels() {
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let recipe = RecipeBuilder::new(30, 100).item_inputs(&[("Bronze Ring", 4)]).fluid_outputs(&[("Steam", 1000)]).build();
+        let description = recipe_description(&egui::Context::default(), 3, "Mixer", &recipe);
+        assert_eq!(description, "Result 3: Mixer, 30 EU/t, 5s, inputs: 4x Bronze Ring, outputs: 1000l Steam");
+
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let row = ui.add(egui::Label::new("3").sense(egui::Sense::click()));
+                selectable_info(&row, true, &description);
+                let ingredient = ui.add(egui::Label::new("1000l Steam").sense(egui::Sense::click()));
+                button_info(&ingredient, "1000l Steam");
+            });
+        });
+        let update = output.platform_output.accesskit_update.unwrap();
+        let node = |name: &str| update.nodes.iter().map(|(_, node)| node).find(|node| node.name() == Some(name)).cloned();
+        let row = node(&description).unwrap();
+        assert_eq!(row.role(), egui::accesskit::Role::ToggleButton);
+        assert_eq!(row.checked(), Some(egui::accesskit::Checked::True));
+        assert_eq!(node("1000l Steam").unwrap().role(), egui::accesskit::Role::Button);
+    }
+
+    #[test]
+    fn test_colors_differ() {
+        for palette in [HighlightPalette::Standard, HighlightPalette::ColourBlindSafe] {
+            for dark_mode in [true, false] {
+                let (input, output) = palette.colors(dark_mode);
+                assert_ne!(input, output);
+            }
+        }
+        let visuals = high_contrast_visuals(true);
+        assert_eq!(visuals.override_text_color, Some(Color32::WHITE));
+        assert_eq!(visuals.panel_fill, Color32::BLACK);
+    }
+}
@@ -1,19 +1,36 @@
-use std::cell::Cell;
 use std::future::Future;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use egui::util::hash;
 use egui::{Color32, Id};
 use egui_extras::{Size, StripBuilder};
+use gtnh_recipe_lib::export::card::Card;
+use gtnh_recipe_lib::export::table::{recipes_to_table, TableFormat};
+use gtnh_recipe_lib::load::LoadError;
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::recipe_id::RecipeId;
+use log::error;
 use std::path::PathBuf;
-use std::rc::Rc;
-use std::{panic, thread};
-use egui::ahash::HashMap;
-use log::{debug, error};
+use crate::accessibility::{button_info, input_color, output_color, recipe_description, selectable_info, DisplaySettings};
+use crate::bookmarks::{BookmarkAction, Bookmarks};
+use crate::canvas::Canvas;
+use crate::comparison::Comparison;
+use crate::i18n::{tr, tr_args, IngredientText, Localization};
+use crate::icons::{IconName, Icons};
+use crate::loader::{BackgroundLoad, LoadSource};
+use crate::machines::MachineList;
+use crate::materials::MaterialsWindow;
+use crate::navigation::{History, Lookup};
+use crate::palette::{CommandPalette, PaletteEntry};
+use crate::planner::Planner;
+use crate::project::{Project, SelectedRecipe, PROJECT_EXTENSION};
+use crate::results_table::{ResultsTable, SortColumn};
+use crate::share::share_menu_ui;
+use crate::shortcuts::{Action, Shortcuts};
+use crate::tabs::{tab_bar_ui, SearchTab, TabAction};
 
-#[derive(Hash, Eq, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Hash, Eq, PartialEq)]
 struct RecipeAndMachine {
     machine_name: String,
-    recipe: gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe,
+    recipe: GregtechRecipe,
 }
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -29,14 +46,43 @@ pub struct GtnhRecipeViewerApp {
     #[serde(skip)]
     search_results: Vec<(
         String,
-        gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe,
+        GregtechRecipe,
     )>,
-    #[serde(skip)]
     selection: std::collections::HashSet<RecipeAndMachine>,
-    #[serde(skip)]
-    opened_windows: std::collections::HashMap<String, bool>,
+    opened_windows: std::collections::HashMap<RecipeId, bool>,
     #[serde(skip)]
     file_channel: (Sender<Vec<u8>>, Receiver<Vec<u8>>),
+    #[serde(skip)]
+    loading: Option<BackgroundLoad>,
+    #[serde(skip)]
+    load_error: Option<String>,
+
+    history: History,
+    /// All search tabs, the active one is kept in `label`, `history`, `search_results` and `results_table`
+    tabs: Vec<SearchTab>,
+    active_tab: usize,
+    planner: Planner,
+    canvas: Canvas,
+    results_table: ResultsTable,
+    bookmarks: Bookmarks,
+    machines: MachineList,
+    comparison: Comparison,
+    materials: MaterialsWindow,
+    icons: Icons,
+    shortcuts: Shortcuts,
+    localization: Localization,
+    display: DisplaySettings,
+    #[serde(skip)]
+    palette: CommandPalette,
+    /// Whether the search box should take the keyboard focus
+    #[serde(skip)]
+    focus_search: bool,
+
+    project_name: String,
+    /// Projects saved in the app storage, by name
+    projects: std::collections::BTreeMap<String, Project>,
+    #[serde(skip)]
+    project_channel: (Sender<Vec<u8>>, Receiver<Vec<u8>>),
 }
 
 impl Default for GtnhRecipeViewerApp {
@@ -50,6 +96,27 @@ impl Default for GtnhRecipeViewerApp {
             selection: Default::default(),
             opened_windows: Default::default(),
             file_channel: channel(),
+            loading: None,
+            load_error: None,
+            history: Default::default(),
+            tabs: vec![Default::default()],
+            active_tab: 0,
+            planner: Default::default(),
+            canvas: Default::default(),
+            results_table: Default::default(),
+            bookmarks: Default::default(),
+            machines: Default::default(),
+            comparison: Default::default(),
+            materials: Default::default(),
+            icons: Default::default(),
+            shortcuts: Default::default(),
+            localization: Default::default(),
+            display: Default::default(),
+            palette: Default::default(),
+            focus_search: false,
+            project_name: String::new(),
+            projects: Default::default(),
+            project_channel: channel(),
         }
     }
 }
@@ -62,58 +129,264 @@ impl GtnhRecipeViewerApp {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+        let mut app: Self = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+        if app.tabs.is_empty() {
+            app.tabs.push(Default::default());
+        }
+        app.active_tab = app.active_tab.min(app.tabs.len() - 1);
+
+        // The web build opens `?dump=<url>` or the dump of the last visit
+        #[cfg(target_arch = "wasm32")]
+        {
+            let source = match crate::web::dump_url() {
+                Some(url) => LoadSource::Url(url),
+                None => LoadSource::LastOpened,
+            };
+            app.start_loading(source, &cc.egui_ctx);
         }
 
-        Default::default()
+        app
     }
 
     fn search(&mut self) {
-        if let Some(recipes) = &self.recipes_json {
+        self.navigate(Lookup::Search(self.label.clone()));
+    }
+
+    fn navigate(&mut self, lookup: Lookup) {
+        self.history.push(lookup);
+        self.show_current_lookup();
+    }
+
+    /// Fills the results table from the current history entry.
+    fn show_current_lookup(&mut self) {
+        if let (Some(recipes), Some(lookup)) = (&self.recipes_json, self.history.current()) {
             //debug!("searching...");
-            self.search_results = recipes.search(&self.label);
+            if let Lookup::Search(keyword) = lookup {
+                self.label = keyword.clone();
+            }
+            self.search_results = lookup.run(recipes);
+            self.results_table.invalidate();
+        }
+    }
+
+    /// Moves the active tab from the app fields back into the tab list.
+    fn store_active_tab(&mut self) {
+        self.tabs[self.active_tab] = SearchTab {
+            label: std::mem::take(&mut self.label),
+            history: std::mem::take(&mut self.history),
+            results_table: std::mem::take(&mut self.results_table),
+            search_results: std::mem::take(&mut self.search_results),
+        };
+    }
+
+    /// Moves tab `index` from the tab list into the app fields.
+    fn load_tab(&mut self, index: usize) {
+        let tab = std::mem::take(&mut self.tabs[index]);
+        self.active_tab = index;
+        self.label = tab.label;
+        self.history = tab.history;
+        self.results_table = tab.results_table;
+        self.search_results = tab.search_results;
+        self.results_table.invalidate();
+        if self.search_results.is_empty() {
+            self.show_current_lookup();
         }
     }
 
+    fn switch_tab(&mut self, index: usize) {
+        if index != self.active_tab && index < self.tabs.len() {
+            self.store_active_tab();
+            self.load_tab(index);
+        }
+    }
+
+    /// Opens a new tab, showing `lookup` if given.
+    fn new_tab(&mut self, lookup: Option<Lookup>) {
+        self.store_active_tab();
+        self.tabs.push(SearchTab::default());
+        self.load_tab(self.tabs.len() - 1);
+        if let Some(lookup) = lookup {
+            self.navigate(lookup);
+        }
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() < 2 || index >= self.tabs.len() {
+            return;
+        }
+        self.store_active_tab();
+        self.tabs.remove(index);
+        let active = if self.active_tab > index { self.active_tab - 1 } else { self.active_tab };
+        self.load_tab(active.min(self.tabs.len() - 1));
+    }
+
+    fn tabs_ui(&mut self, ui: &mut egui::Ui) {
+        let titles = (0..self.tabs.len())
+            .map(|index| {
+                if index == self.active_tab {
+                    SearchTab::title(ui.ctx(), &self.history)
+                } else {
+                    SearchTab::title(ui.ctx(), &self.tabs[index].history)
+                }
+            })
+            .collect::<Vec<String>>();
+        match tab_bar_ui(ui, &titles, self.active_tab) {
+            Some(TabAction::Switch(index)) => self.switch_tab(index),
+            Some(TabAction::Close(index)) => self.close_tab(index),
+            Some(TabAction::New) => self.new_tab(None),
+            None => {}
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let mut actions = self.shortcuts.pressed(ctx);
+        if self.palette.open {
+            // The palette handles its own keys
+            actions.retain(|action| *action == Action::CommandPalette);
+        }
+        for action in actions {
+            self.run_action(action, ctx);
+        }
+    }
+
+    fn run_action(&mut self, action: Action, ctx: &egui::Context) {
+        match action {
+            Action::CommandPalette => self.palette.toggle(),
+            Action::FocusSearch => self.focus_search = true,
+            Action::NextRow => self.results_table.move_cursor(1, &self.search_results),
+            Action::PreviousRow => self.results_table.move_cursor(-1, &self.search_results),
+            Action::OpenDetails => {
+                if let Some(row_index) = self.results_table.cursor_row(&self.search_results) {
+                    self.toggle_selection(row_index);
+                }
+            }
+            Action::LookupRecipes | Action::LookupUses => {
+                let hovered = ctx.data(|data| data.get_temp::<String>(Id::new(HOVERED_INGREDIENT)));
+                let name = hovered.or_else(|| {
+                    let row_index = self.results_table.cursor_row(&self.search_results)?;
+                    let recipe = &self.search_results[row_index].1;
+                    recipe.item_outputs.first().map(|item| item.get_name())
+                        .or_else(|| recipe.fluid_outputs.first().map(|fluid| fluid.get_name()))
+                });
+                if let Some(name) = name {
+                    self.navigate(if action == Action::LookupRecipes { Lookup::Recipes(name) } else { Lookup::Uses(name) });
+                }
+            }
+            Action::CloseWindows => {
+                self.selection.clear();
+                self.opened_windows.clear();
+            }
+            Action::Back => {
+                self.history.back();
+                self.show_current_lookup();
+            }
+            Action::Forward => {
+                self.history.forward();
+                self.show_current_lookup();
+            }
+            Action::NewTab => self.new_tab(None),
+            Action::CloseTab => self.close_tab(self.active_tab),
+            Action::ToggleFavourites => self.bookmarks.open = !self.bookmarks.open,
+            Action::ToggleMachines => self.machines.open = !self.machines.open,
+            Action::TogglePlanner => self.planner.open = !self.planner.open,
+            Action::ToggleCanvas => {
+                self.canvas.open = !self.canvas.open;
+                if self.canvas.open {
+                    self.canvas.add_recipes(&self.selected_recipes());
+                }
+            }
+            Action::ToggleMaterials => self.materials.open = !self.materials.open,
+            Action::ToggleComparison => {
+                self.comparison.open = !self.comparison.open;
+                if self.comparison.open {
+                    self.comparison.add_recipes(&self.selected_recipes());
+                }
+            }
+        }
+    }
+
+    fn navigation_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.history.can_go_back(), egui::Button::new("⏴")).on_hover_text(tr(ui.ctx(), "Back")).clicked() {
+                self.history.back();
+                self.show_current_lookup();
+            }
+            if ui.add_enabled(self.history.can_go_forward(), egui::Button::new("⏵")).on_hover_text(tr(ui.ctx(), "Forward")).clicked() {
+                self.history.forward();
+                self.show_current_lookup();
+            }
+            if let Some(lookup) = self.history.current() {
+                ui.label(lookup.title(ui.ctx()));
+            }
+        });
+    }
+
     fn details_windows(&mut self, ui: &mut egui::Ui) {
+        let mut lookup = None;
         for selection in &self.selection {
             let recipe = &selection.recipe;
-            let recipe_hash = hash(format!("{}", recipe));
-            let hash_str = recipe_hash.to_string();
+            let id = recipe.id(&selection.machine_name);
 
-            ui.push_id(hash_str.clone(), |ui| {
-                egui::Window::new("Recipe Details")
+            ui.push_id(&id, |ui| {
+                // The machine in the title tells the windows apart, also for screen readers
+                egui::Window::new(format!("{} – {}", selection.machine_name, tr(ui.ctx(), "Recipe Details")))
                     .default_width(400.0)
-                    .id(Id::from(hash_str.clone()))
-                    .open(self.opened_windows.get_mut(&hash_str).unwrap())
+                    .id(Id::new(&id))
+                    .open(self.opened_windows.entry(id.clone()).or_insert(true))
                     .show(ui.ctx(), |ui| {
-                        ui.heading(format!("{}", selection.machine_name));
+                        ui.horizontal(|ui| {
+                            ui.heading(selection.machine_name.to_string());
+                            let starred = self.bookmarks.is_machine_starred(&selection.machine_name);
+                            if ui.selectable_label(starred, tr(ui.ctx(), "★ machine")).on_hover_text(tr(ui.ctx(), "Favourite machine")).clicked() {
+                                self.bookmarks.toggle_machine(&selection.machine_name);
+                            }
+                        });
+                        self.bookmarks.recipe_ui(ui, &selection.machine_name, recipe);
                         ui.label(format!("{}s, {}EU/t", recipe.duration, recipe.eut));
-                        ui.heading("Item Inputs:");
+                        ui.heading(egui::RichText::new(tr(ui.ctx(), "Item Inputs:")).color(input_color(ui)));
                         for item in &recipe.item_inputs {
-                            ui.label(format!("{}", item));
+                            ingredient_label(ui, &self.icons, item, item.get_name(), &mut lookup);
                         }
-                        ui.heading("Item Outputs:");
+                        ui.heading(egui::RichText::new(tr(ui.ctx(), "Item Outputs:")).color(output_color(ui)));
                         for item in &recipe.item_outputs {
-                            ui.label(format!("{}", item));
+                            ingredient_label(ui, &self.icons, item, item.get_name(), &mut lookup);
                         }
 
-                        ui.heading("Fluid Inputs:");
+                        ui.heading(egui::RichText::new(tr(ui.ctx(), "Fluid Inputs:")).color(input_color(ui)));
                         for fluid in &recipe.fluid_inputs {
-                            ui.label(format!("{}", fluid));
+                            ingredient_label(ui, &self.icons, fluid, fluid.get_name(), &mut lookup);
                         }
-                        ui.heading("Fluid Outputs:");
+                        ui.heading(egui::RichText::new(tr(ui.ctx(), "Fluid Outputs:")).color(output_color(ui)));
                         for fluid in &recipe.fluid_outputs {
-                            ui.label(format!("{}", fluid));
+                            ingredient_label(ui, &self.icons, fluid, fluid.get_name(), &mut lookup);
                         }
 
+                        ui.horizontal(|ui| {
+                            if ui.button(tr(ui.ctx(), "Add to planner")).clicked() {
+                                self.planner.add_recipe(&selection.machine_name, recipe);
+                            }
+                            if ui.button(tr(ui.ctx(), "Compare")).clicked() {
+                                self.comparison.add_recipes(&[(selection.machine_name.clone(), recipe.clone())]);
+                            }
+                            if ui.button(tr(ui.ctx(), "Bill of materials")).clicked() {
+                                self.materials.show_recipe(&selection.machine_name, recipe);
+                            }
+                        });
+
                         ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
                             ui.small(format!("{}", recipe));
                         });
                     });
             });
         }
+        match lookup {
+            Some((lookup, true)) => self.new_tab(Some(lookup)),
+            Some((lookup, false)) => self.navigate(lookup),
+            None => {}
+        }
     }
 
     fn table_ui(&mut self, ui: &mut egui::Ui, reset: bool) {
@@ -124,13 +397,17 @@ impl GtnhRecipeViewerApp {
             .size
             .max(ui.spacing().interact_size.y);
 
+        let header_height = 2.0 * text_height + ui.spacing().item_spacing.y + 4.0;
         let available_height = ui.available_height();
+        let ctx = ui.ctx().clone();
         let mut table = TableBuilder::new(ui)
             .striped(true)
             .resizable(true)
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .column(Column::auto())
             .column(Column::remainder().clip(true))
+            .column(Column::initial(70.0).clip(true))
+            .column(Column::initial(70.0).clip(true))
             .column(Column::remainder().clip(true))
             .column(Column::remainder().clip(true))
             .column(Column::remainder().clip(true))
@@ -139,123 +416,343 @@ impl GtnhRecipeViewerApp {
             .max_scroll_height(available_height);
 
         table = table.sense(egui::Sense::click());
+        if std::mem::take(&mut self.results_table.scroll_to_cursor) {
+            if let Some(cursor) = self.results_table.cursor {
+                table = table.scroll_to_row(cursor, None);
+            }
+        }
 
         if reset {
             table.reset();
         }
 
+        let mut clicked_row = None;
         table
-            .header(20.0, |mut header| {
+            .header(header_height, |mut header| {
+                let results_table = &mut self.results_table;
                 header.col(|ui| {
-                    ui.strong("Row");
+                    ui.strong(tr(ui.ctx(), "Row"));
                 });
                 header.col(|ui| {
-                    ui.strong("Machine");
+                    results_table.header_ui(ui, tr(ui.ctx(), "Machine"), None, Some(SortColumn::Machine), |filters| &mut filters.machine);
                 });
                 header.col(|ui| {
-                    ui.strong("Items (Input)");
+                    results_table.header_ui(ui, "EU/t", None, Some(SortColumn::Eut), |filters| &mut filters.eut);
                 });
                 header.col(|ui| {
-                    ui.strong("Fluids (Input)");
+                    results_table.header_ui(ui, tr(ui.ctx(), "Duration"), None, Some(SortColumn::Duration), |filters| &mut filters.duration);
                 });
                 header.col(|ui| {
-                    ui.strong("Items (Output)");
+                    results_table.header_ui(ui, tr(ui.ctx(), "Items (Input)"), Some(input_color(ui)), Some(SortColumn::Inputs), |filters| &mut filters.item_inputs);
                 });
                 header.col(|ui| {
-                    ui.strong("Fluids (Output)");
+                    results_table.header_ui(ui, tr(ui.ctx(), "Fluids (Input)"), Some(input_color(ui)), None, |filters| &mut filters.fluid_inputs);
+                });
+                header.col(|ui| {
+                    results_table.header_ui(ui, tr(ui.ctx(), "Items (Output)"), Some(output_color(ui)), Some(SortColumn::Outputs), |filters| &mut filters.item_outputs);
+                });
+                header.col(|ui| {
+                    results_table.header_ui(ui, tr(ui.ctx(), "Fluids (Output)"), Some(output_color(ui)), None, |filters| &mut filters.fluid_outputs);
                 });
             })
-            .body(|mut body| {
-                body.rows(text_height, self.search_results.len(), |mut row| {
-                    let row_index = row.index();
-                    let search_result = self.search_results.iter().nth(row_index).unwrap();
+            .body(|body| {
+                let cursor = self.results_table.cursor;
+                let rows = self.results_table.rows(&self.search_results);
+                body.rows(text_height, rows.len(), |mut row| {
+                    let at_cursor = cursor == Some(row.index());
+                    let row_index = rows[row.index()];
+                    let search_result = self.search_results.get(row_index).unwrap();
 
                     let recipe_and_machine = RecipeAndMachine {
                         machine_name: search_result.0.clone(),
                         recipe: search_result.1.clone(),
                     };
 
-                    let recipe_hash = hash(format!("{}", recipe_and_machine.recipe));
-                    let hash_str = recipe_hash.to_string();
-                    if let Some(open) = self.opened_windows.get(&hash_str) {
-                        if *open == false {
+                    let id = recipe_and_machine.recipe.id(&recipe_and_machine.machine_name);
+                    if let Some(open) = self.opened_windows.get(&id) {
+                        if !*open {
                             self.selection.remove(&recipe_and_machine);
                         }
                     }
 
                     row.set_selected(self.selection.contains(&recipe_and_machine));
 
+                    let starred = self.bookmarks.is_starred(&search_result.0, &search_result.1);
                     row.col(|ui| {
-                        ui.label(row_index.to_string());
+                        let label = match (at_cursor, starred) {
+                            (true, true) => format!("▶ ★ {}", row_index),
+                            (true, false) => format!("▶ {}", row_index),
+                            (false, true) => format!("★ {}", row_index),
+                            (false, false) => row_index.to_string(),
+                        };
+                        ui.label(label);
                     });
                     row.col(|ui| {
-                        ui.label(format!("{}", search_result.0));
+                        ui.label(search_result.0.to_string());
                     });
                     row.col(|ui| {
-                        ui.label(
-                            search_result
-                                .1
-                                .item_inputs
-                                .iter()
-                                .map(|item| format!("{}", item))
-                                .collect::<Vec<String>>()
-                                .join(" + "),
-                        );
+                        ui.label(search_result.1.eut.to_string());
                     });
                     row.col(|ui| {
-                        ui.label(
-                            search_result
-                                .1
-                                .fluid_inputs
-                                .iter()
-                                .map(|fluid| format!("{}", fluid))
-                                .collect::<Vec<String>>()
-                                .join(" + "),
-                        );
+                        ui.label(format!("{}s", search_result.1.duration as f64 / 20.0));
                     });
                     row.col(|ui| {
-                        ui.label(
-                            search_result
-                                .1
-                                .item_outputs
-                                .iter()
-                                .map(|item| format!("{}", item))
-                                .collect::<Vec<String>>()
-                                .join(" + "),
-                        );
+                        self.icons.ingredients_ui(ui, &search_result.1.item_inputs);
                     });
                     row.col(|ui| {
-                        ui.label(
-                            search_result
-                                .1
-                                .fluid_outputs
-                                .iter()
-                                .map(|fluid| format!("{}", fluid))
-                                .collect::<Vec<String>>()
-                                .join(" + "),
-                        );
+                        self.icons.ingredients_ui(ui, &search_result.1.fluid_inputs);
+                    });
+                    row.col(|ui| {
+                        self.icons.ingredients_ui(ui, &search_result.1.item_outputs);
+                    });
+                    row.col(|ui| {
+                        self.icons.ingredients_ui(ui, &search_result.1.fluid_outputs);
                     });
 
-                    self.toggle_row_selection(row_index, &row.response());
+                    let selected = self.selection.contains(&recipe_and_machine);
+                    selectable_info(&row.response(), selected, &recipe_description(&ctx, row_index, &search_result.0, &search_result.1));
+                    if row.response().clicked() {
+                        clicked_row = Some(row_index);
+                    }
+                    row.response().context_menu(|ui| {
+                        if ui.button(tr(ui.ctx(), if starred { "Remove favourite" } else { "Add favourite" })).clicked() {
+                            let (machine_name, recipe) = &self.search_results[row_index];
+                            self.bookmarks.toggle(machine_name, recipe);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr(ui.ctx(), "Add to planner")).clicked() {
+                            let (machine_name, recipe) = &self.search_results[row_index];
+                            self.planner.add_recipe(machine_name, recipe);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr(ui.ctx(), "Add to comparison")).clicked() {
+                            self.comparison.add_recipes(&self.search_results[row_index..=row_index]);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr(ui.ctx(), "Bill of materials")).clicked() {
+                            let (machine_name, recipe) = &self.search_results[row_index];
+                            self.materials.show_recipe(machine_name, recipe);
+                            ui.close_menu();
+                        }
+                    });
                 });
             });
+        if let Some(row_index) = clicked_row {
+            self.toggle_selection(row_index);
+        }
     }
 
-    fn toggle_row_selection(&mut self, row_index: usize, row_response: &egui::Response) {
-        if row_response.clicked() {
-            let search_result = self.search_results.iter().nth(row_index).unwrap();
-            let recipe_and_machine = RecipeAndMachine {
-                recipe: search_result.1.clone(),
-                machine_name: search_result.0.clone(),
-            };
-            let recipe_hash = hash(format!("{}", recipe_and_machine.recipe));
-            let hash_str = recipe_hash.to_string();
-            if self.selection.contains(&recipe_and_machine) {
-                self.selection.remove(&recipe_and_machine);
-                self.opened_windows.remove(&hash_str);
-            } else {
-                self.selection.insert(recipe_and_machine);
-                self.opened_windows.insert(hash_str, true);
+    /// Selects search result `row_index` and opens its details window, or closes it again.
+    fn toggle_selection(&mut self, row_index: usize) {
+        let search_result = self.search_results.get(row_index).unwrap();
+        let recipe_and_machine = RecipeAndMachine {
+            recipe: search_result.1.clone(),
+            machine_name: search_result.0.clone(),
+        };
+        let id = recipe_and_machine.recipe.id(&recipe_and_machine.machine_name);
+        if self.selection.contains(&recipe_and_machine) {
+            self.selection.remove(&recipe_and_machine);
+            self.opened_windows.remove(&id);
+        } else {
+            self.selection.insert(recipe_and_machine);
+            self.opened_windows.insert(id, true);
+        }
+    }
+
+    fn selected_recipes(&self) -> Vec<(String, GregtechRecipe)> {
+        let mut recipes = self
+            .selection
+            .iter()
+            .map(|selection| (selection.machine_name.clone(), selection.recipe.clone()))
+            .collect::<Vec<_>>();
+        recipes.sort_by_key(|(machine_name, recipe)| (machine_name.clone(), format!("{}", recipe)));
+        recipes
+    }
+
+    fn current_project(&self) -> Project {
+        let mut selection = self
+            .selection
+            .iter()
+            .map(|selection| SelectedRecipe {
+                machine_name: selection.machine_name.clone(),
+                recipe: selection.recipe.clone(),
+                window_open: self.opened_windows.get(&selection.recipe.id(&selection.machine_name)).copied().unwrap_or(false),
+            })
+            .collect::<Vec<SelectedRecipe>>();
+        selection.sort_by_key(|selected| (selected.machine_name.clone(), format!("{}", selected.recipe)));
+        let mut tabs = self.tabs.clone();
+        tabs[self.active_tab] = SearchTab {
+            label: self.label.clone(),
+            history: self.history.clone(),
+            results_table: self.results_table.clone(),
+            search_results: vec![],
+        };
+        Project {
+            name: self.project_name.clone(),
+            search: self.label.clone(),
+            history: self.history.clone(),
+            tabs,
+            active_tab: self.active_tab,
+            selection,
+            planner: self.planner.clone(),
+            canvas: self.canvas.clone(),
+            comparison: self.comparison.clone(),
+        }
+    }
+
+    fn apply_project(&mut self, project: Project) {
+        self.project_name = project.name;
+        if project.tabs.is_empty() {
+            self.tabs = vec![SearchTab { label: project.search, history: project.history, ..Default::default() }];
+            self.active_tab = 0;
+        } else {
+            self.active_tab = project.active_tab.min(project.tabs.len() - 1);
+            self.tabs = project.tabs;
+        }
+        self.selection.clear();
+        self.opened_windows.clear();
+        for selected in project.selection {
+            self.opened_windows.insert(selected.recipe.id(&selected.machine_name), selected.window_open);
+            self.selection.insert(RecipeAndMachine {
+                machine_name: selected.machine_name,
+                recipe: selected.recipe,
+            });
+        }
+        self.planner = project.planner;
+        self.canvas = project.canvas;
+        self.comparison = project.comparison;
+        self.load_tab(self.active_tab);
+    }
+
+    fn project_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.horizontal(|ui| {
+            ui.label(tr(ui.ctx(), "Name:"));
+            ui.text_edit_singleline(&mut self.project_name);
+        });
+        if ui.add_enabled(!self.project_name.is_empty(), egui::Button::new(tr(ui.ctx(), "Save"))).clicked() {
+            self.projects.insert(self.project_name.clone(), self.current_project());
+            ui.close_menu();
+        }
+        let mut opened = None;
+        let mut deleted = None;
+        ui.add_enabled_ui(!self.projects.is_empty(), |ui| {
+            ui.menu_button(tr(ui.ctx(), "Open"), |ui| {
+                for name in self.projects.keys() {
+                    ui.horizontal(|ui| {
+                        if ui.button(name).clicked() {
+                            opened = Some(name.clone());
+                            ui.close_menu();
+                        }
+                        if ui.small_button("🗑").on_hover_text(tr(ui.ctx(), "Delete project")).clicked() {
+                            deleted = Some(name.clone());
+                        }
+                    });
+                }
+            });
+        });
+        if let Some(project) = opened.and_then(|name| self.projects.get(&name).cloned()) {
+            self.apply_project(project);
+        }
+        if let Some(name) = deleted {
+            self.projects.remove(&name);
+        }
+        if ui.button(tr(ui.ctx(), "New")).clicked() {
+            self.apply_project(Project::default());
+            ui.close_menu();
+        }
+
+        ui.separator();
+        if ui.button(tr(ui.ctx(), "Export to file…")).clicked() {
+            self.save_project_file(ui.ctx());
+            ui.close_menu();
+        }
+        if ui.button(tr(ui.ctx(), "Import from file…")).clicked() {
+            self.open_project_file(ctx);
+            ui.close_menu();
+        }
+    }
+
+    fn save_project_file(&self, ctx: &egui::Context) {
+        let project = self.current_project();
+        match project.to_json() {
+            Ok(json) => {
+                let task = rfd::AsyncFileDialog::new()
+                    .set_title(tr(ctx, "Export project"))
+                    .set_file_name(project.file_name())
+                    .save_file();
+                execute(async move {
+                    if let Some(file) = task.await {
+                        if let Err(err) = file.write(json.as_bytes()).await {
+                            error!("unable to write project: {}", err);
+                        }
+                    }
+                });
+            }
+            Err(err) => error!("unable to export project: {}", err),
+        }
+    }
+
+    fn open_project_file(&self, ctx: &egui::Context) {
+        let sender = self.project_channel.0.clone();
+        let task = rfd::AsyncFileDialog::new()
+            .set_title(tr(ctx, "Import project"))
+            .add_filter("project", &[PROJECT_EXTENSION, "json"])
+            .pick_file();
+        let ctx = ctx.clone();
+        execute(async move {
+            if let Some(file) = task.await {
+                let _ = sender.send(file.read().await);
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Replaces the loaded recipes with the ones parsed in the background from `source`.
+    fn start_loading(&mut self, source: LoadSource, ctx: &egui::Context) {
+        if let Some(loading) = &self.loading {
+            loading.cancel();
+        }
+        self.recipes_json = None;
+        self.search_results.clear();
+        for tab in &mut self.tabs {
+            tab.search_results.clear();
+        }
+        self.results_table.invalidate();
+        self.load_error = None;
+        self.loading = Some(BackgroundLoad::start(source, ctx));
+    }
+
+    /// Opens a dump or binary cache from disk, e.g. passed on the command line.
+    pub fn open_path(&mut self, path: PathBuf) {
+        if let Some(loading) = self.loading.take() {
+            loading.cancel();
+        }
+        self.recipes_json = None;
+        self.load_error = None;
+        self.filename = Some(path);
+    }
+
+    /// Loads dropped dumps, caches and project files.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        for file in ctx.input_mut(|input| std::mem::take(&mut input.raw.dropped_files)) {
+            let is_project = file.name.ends_with(PROJECT_EXTENSION)
+                || file.path.as_ref().is_some_and(|path| path.to_string_lossy().ends_with(PROJECT_EXTENSION));
+            if is_project {
+                let bytes = match (&file.bytes, &file.path) {
+                    (Some(bytes), _) => Ok(bytes.to_vec()),
+                    (None, Some(path)) => std::fs::read(path),
+                    (None, None) => continue,
+                };
+                match bytes.map_err(|err| err.to_string()).and_then(|bytes| Project::from_json(&bytes).map_err(|err| err.to_string())) {
+                    Ok(project) => self.apply_project(project),
+                    Err(err) => error!("unable to load project: {}", err),
+                }
+            } else if let Some(bytes) = file.bytes {
+                // Web builds only get the contents
+                self.filename = None;
+                self.start_loading(LoadSource::Bytes(bytes.to_vec()), ctx);
+            } else if let Some(path) = file.path {
+                self.open_path(path);
             }
         }
     }
@@ -263,8 +760,9 @@ impl GtnhRecipeViewerApp {
     fn open_file(&mut self, ctx: &egui::Context) {
         let sender = self.file_channel.0.clone();
         let task = rfd::AsyncFileDialog::new()
-            .set_title("Select recipes.json please")
+            .set_title(tr(ctx, "Select recipes.json please"))
             .add_filter("json", &["json", "json"])
+            .add_filter("binary cache", &["bin"])
             .pick_file();
         let ctx = ctx.clone();
         execute(async move {
@@ -284,47 +782,257 @@ impl eframe::App for GtnhRecipeViewerApp {
         // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
-        if let None = self.recipes_json {
-            if let Some(path) = &self.filename {
-                self.recipes_json = Some(gtnh_recipe_lib::load_file(path));
-            } else {
-                if let Ok(text) = self.file_channel.1.try_recv() {
-                    self.recipes_json = Some(gtnh_recipe_lib::load_bytes(text));
+        self.display.apply(ctx);
+        self.localization.apply(ctx);
+        self.handle_dropped_files(ctx);
+        self.icons.update(ctx);
+        self.handle_shortcuts(ctx);
+        // Set again below while an ingredient is hovered
+        ctx.data_mut(|data| data.remove::<String>(Id::new(HOVERED_INGREDIENT)));
+        if let Ok(bytes) = self.file_channel.1.try_recv() {
+            self.start_loading(LoadSource::Bytes(bytes), ctx);
+        }
+        if self.recipes_json.is_none() && self.loading.is_none() && self.load_error.is_none() {
+            if let Some(path) = self.filename.clone() {
+                self.start_loading(LoadSource::Path(path), ctx);
+            }
+        }
+        if let Some(result) = self.loading.as_ref().and_then(|loading| loading.poll()) {
+            self.loading = None;
+            match result {
+                Ok(recipes) => {
+                    self.recipes_json = Some(recipes);
+                    self.palette.invalidate();
+                    self.machines.invalidate();
+                    self.materials.invalidate();
+                    self.canvas.invalidate();
+                    self.show_current_lookup();
+                }
+                Err(LoadError::Cancelled) => self.filename = None,
+                Err(err) => {
+                    error!("{}", err);
+                    self.filename = None;
+                    self.load_error = Some(err.to_string());
                 }
             }
         }
 
+        if let Ok(bytes) = self.project_channel.1.try_recv() {
+            match Project::from_json(&bytes) {
+                Ok(project) => self.apply_project(project),
+                Err(err) => error!("unable to load project: {}", err),
+            }
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
 
             egui::menu::bar(ui, |ui| {
                 // NOTE: no File->Quit on web pages!
                 let is_web = cfg!(target_arch = "wasm32");
+                #[cfg(target_arch = "wasm32")]
+                {
+                    ui.menu_button(tr(ui.ctx(), "Cache"), |ui| {
+                        if ui.button(tr(ui.ctx(), "Forget cached dumps")).on_hover_text(tr(ui.ctx(), "Dumps are kept in the browser to open them on the next visit")).clicked() {
+                            execute(async {
+                                if let Err(err) = crate::web::cache_clear().await {
+                                    error!("unable to clear the dump cache: {}", err);
+                                }
+                            });
+                            ui.close_menu();
+                        }
+                    });
+                    ui.add_space(16.0);
+                }
                 if !is_web {
-                    ui.menu_button("File", |ui| {
-                        if ui.button("Open new file").clicked() {
+                    ui.menu_button(tr(ui.ctx(), "File"), |ui| {
+                        if ui.button(tr(ui.ctx(), "Open new file")).clicked() {
                             self.filename = None;
                             self.recipes_json = None;
                             self.open_file(ctx);
                         }
-                        if ui.button("Quit").clicked() {
+                        if ui.button(tr(ui.ctx(), "Quit")).clicked() {
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
                     });
                     ui.add_space(16.0);
                 }
 
+                ui.menu_button(tr(ui.ctx(), "Project"), |ui| {
+                    self.project_menu(ui, ctx);
+                });
+                ui.add_space(16.0);
+
+                ui.menu_button(tr(ui.ctx(), "Export"), |ui| {
+                    for format in [TableFormat::Csv, TableFormat::Tsv] {
+                        let extension = format.extension().to_uppercase();
+                        if ui
+                            .add_enabled(!self.search_results.is_empty(), egui::Button::new(tr_args(ui.ctx(), "Search results as {}", &[&extension])))
+                            .clicked()
+                        {
+                            export_table(ui.ctx(), &self.search_results, "search_results", format);
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(!self.selection.is_empty(), egui::Button::new(tr_args(ui.ctx(), "Selection as {}", &[&extension])))
+                            .clicked()
+                        {
+                            export_table(ui.ctx(), &self.selected_recipes(), "selection", format);
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+                        ui.menu_button(tr(ui.ctx(), "Share selection"), |ui| {
+                            let cards = self
+                                .selected_recipes()
+                                .iter()
+                                .map(|(machine_name, recipe)| Card::from_recipe(machine_name, recipe))
+                                .collect::<Vec<Card>>();
+                            share_menu_ui(ui, &cards, "selection");
+                        });
+                    });
+                    let planner_card = self.planner.card();
+                    ui.add_enabled_ui(planner_card.is_some(), |ui| {
+                        ui.menu_button(tr(ui.ctx(), "Share planner result"), |ui| {
+                            if let Some(card) = planner_card {
+                                share_menu_ui(ui, &[card], "production_chain");
+                            }
+                        });
+                    });
+                });
+                ui.add_space(16.0);
+
+                ui.menu_button(tr(ui.ctx(), "Icons"), |ui| {
+                    self.icons.menu_ui(ui);
+                });
+                ui.add_space(16.0);
+
+                ui.menu_button(tr(ui.ctx(), "View"), |ui| {
+                    if ui.button(tr(ui.ctx(), "Display settings…")).clicked() {
+                        self.display.open = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if self.localization.menu_ui(ui) {
+                        // Filters match the shown names
+                        self.results_table.invalidate();
+                        for tab in &mut self.tabs {
+                            tab.results_table.invalidate();
+                        }
+                    }
+                });
+                ui.add_space(16.0);
+
+                ui.menu_button(tr(ui.ctx(), "Keyboard"), |ui| {
+                    let palette = egui::Button::new(tr(ui.ctx(), "Command palette")).shortcut_text(self.shortcuts.format(ctx, Action::CommandPalette));
+                    if ui.add(palette).clicked() {
+                        self.palette.toggle();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr(ui.ctx(), "Shortcuts…")).clicked() {
+                        self.shortcuts.open = true;
+                        ui.close_menu();
+                    }
+                });
+                ui.add_space(16.0);
+
+                ui.toggle_value(&mut self.bookmarks.open, tr(ui.ctx(), "Favourites"));
+                ui.toggle_value(&mut self.machines.open, tr(ui.ctx(), "Machines"));
+                ui.toggle_value(&mut self.planner.open, tr(ui.ctx(), "Planner"));
+                if ui.toggle_value(&mut self.canvas.open, tr(ui.ctx(), "Canvas")).clicked() && self.canvas.open {
+                    let selection = self.selected_recipes();
+                    self.canvas.add_recipes(&selection);
+                }
+                if ui.toggle_value(&mut self.comparison.open, tr(ui.ctx(), "Compare")).on_hover_text(tr(ui.ctx(), "Compare the selected recipes")).clicked() && self.comparison.open {
+                    let selection = self.selected_recipes();
+                    self.comparison.add_recipes(&selection);
+                }
+                ui.add_space(16.0);
+
                 egui::widgets::global_dark_light_mode_buttons(ui);
             });
         });
 
+        let bookmark_action = egui::SidePanel::left("bookmarks_panel")
+            .default_width(250.0)
+            .show_animated(ctx, self.bookmarks.open, |ui| {
+                ui.heading(tr(ui.ctx(), "Favourites"));
+                egui::ScrollArea::vertical().show(ui, |ui| self.bookmarks.sidebar_ui(ui)).inner
+            })
+            .and_then(|response| response.inner);
+        match bookmark_action {
+            Some(BookmarkAction::OpenRecipe(machine_name, recipe)) => {
+                self.opened_windows.insert(recipe.id(&machine_name), true);
+                self.selection.insert(RecipeAndMachine { machine_name, recipe });
+            }
+            Some(BookmarkAction::ShowMachine(machine_name)) => self.navigate(Lookup::Machine(machine_name)),
+            None => {}
+        }
+
+        let current_machine = match self.history.current() {
+            Some(Lookup::Machine(name)) => Some(name.as_str()),
+            _ => None,
+        };
+        let machine = egui::SidePanel::left("machines_panel")
+            .default_width(250.0)
+            .show_animated(ctx, self.machines.open, |ui| {
+                ui.heading(tr(ui.ctx(), "Machines"));
+                self.machines.sidebar_ui(ui, self.recipes_json.as_ref(), current_machine)
+            })
+            .and_then(|response| response.inner);
+        if let Some(machine) = machine {
+            self.navigate(Lookup::Machine(machine));
+        }
+
+        egui::SidePanel::right("planner_panel")
+            .default_width(400.0)
+            .show_animated(ctx, self.planner.open, |ui| {
+                ui.heading(tr(ui.ctx(), "Planner"));
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.planner.ui(ui, &self.icons);
+                });
+            });
+
+        if self.canvas.open {
+            let selection = self.selected_recipes();
+            self.canvas.window(ctx, self.recipes_json.as_ref(), &selection);
+        }
+        if self.comparison.open {
+            self.comparison.window(ctx);
+        }
+        if self.materials.open {
+            self.materials.window(ctx, self.recipes_json.as_ref());
+        }
+        if self.shortcuts.open {
+            self.shortcuts.window(ctx);
+        }
+        if self.display.open {
+            self.display.window(ctx);
+        }
+        match self.palette.ui(ctx, self.recipes_json.as_ref(), &self.shortcuts) {
+            Some(PaletteEntry::Action(action)) => self.run_action(action, ctx),
+            Some(PaletteEntry::Machine(name)) => self.navigate(Lookup::Machine(name)),
+            Some(PaletteEntry::Ingredient(name)) => self.navigate(Lookup::Recipes(name)),
+            None => {}
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
-            ui.heading("GTNH recipe viewer");
+            ui.heading(tr(ui.ctx(), "GTNH recipe viewer"));
 
 
-            if self.recipes_json.is_none() {
-                if ui.button("Open recipes.json").clicked() {
+            if let Some(loading) = &self.loading {
+                ui.label(tr(ui.ctx(), "Loading recipes…"));
+                ui.add(egui::ProgressBar::new(loading.progress()).show_percentage().animate(true));
+                if ui.button(tr(ui.ctx(), "Cancel")).clicked() {
+                    loading.cancel();
+                }
+            } else if self.recipes_json.is_none() {
+                if let Some(err) = &self.load_error {
+                    ui.colored_label(Color32::RED, tr_args(ui.ctx(), "Loading failed: {}", &[err]));
+                }
+                if ui.button(tr(ui.ctx(), "Open recipes.json")).clicked() {
                     self.open_file(ctx);
                 }
 
@@ -338,9 +1046,13 @@ impl eframe::App for GtnhRecipeViewerApp {
                     egui::warn_if_debug_build(ui);
                 });
             } else {
+                self.tabs_ui(ui);
                 ui.horizontal(|ui| {
-                    ui.label("Search: ");
+                    ui.label(tr(ui.ctx(), "Search: "));
                     let textedit_response = ui.text_edit_singleline(&mut self.label);
+                    if std::mem::take(&mut self.focus_search) {
+                        textedit_response.request_focus();
+                    }
                     if textedit_response.lost_focus() {
                         textedit_response.ctx.input(|i| {
                             if i.key_pressed(egui::Key::Enter) {
@@ -350,11 +1062,20 @@ impl eframe::App for GtnhRecipeViewerApp {
                     }
                 });
 
-                if ui.button("Search").clicked() {
+                if ui.button(tr(ui.ctx(), "Search")).clicked() {
                     //search recipes
                     self.search();
                 }
 
+                self.navigation_ui(ui);
+                if !self.search_results.is_empty() {
+                    ui.horizontal(|ui| {
+                        self.results_table.machine_filter_ui(ui, &self.search_results);
+                        let shown = self.results_table.rows(&self.search_results).len();
+                        ui.label(tr_args(ui.ctx(), "{} of {} results shown", &[&shown, &self.search_results.len()]));
+                    });
+                }
+
                 ui.separator();
 
                 StripBuilder::new(ui)
@@ -368,7 +1089,7 @@ impl eframe::App for GtnhRecipeViewerApp {
                                     self.table_ui(ui, false);
                                 });
                             } else {
-                                ui.label("Search something!");
+                                ui.label(tr(ui.ctx(), "Search something!"));
                             }
 
                             if !&self.selection.is_empty() {
@@ -378,13 +1099,12 @@ impl eframe::App for GtnhRecipeViewerApp {
                             ui.separator();
 
                             if let Some(recipes) = &self.recipes_json {
-                                ui.label(format!(
+                                ui.label(tr_args(ui.ctx(),
                                     "Total recipes loaded: {}, search results: {}",
-                                    recipes.get_recipe_count(),
-                                    self.search_results.len()
+                                    &[&recipes.get_recipe_count(), &self.search_results.len()],
                                 ));
                             } else {
-                                ui.colored_label(Color32::RED, "No recipes loaded!");
+                                ui.colored_label(Color32::RED, tr(ui.ctx(), "No recipes loaded!"));
                             }
                         });
                         strip.cell(|ui| {
@@ -401,6 +1121,8 @@ impl eframe::App for GtnhRecipeViewerApp {
                     });
             }
         });
+
+        preview_hovered_files(ctx);
     }
 
     /// Called by the framework to save state before shutdown.
@@ -409,6 +1131,69 @@ impl eframe::App for GtnhRecipeViewerApp {
     }
 }
 
+/// Asks where to save `recipes` as a table and writes it there.
+fn export_table(ctx: &egui::Context, recipes: &[(String, GregtechRecipe)], name: &str, format: TableFormat) {
+    match recipes_to_table(recipes, format) {
+        Ok(table) => {
+            let task = rfd::AsyncFileDialog::new()
+                .set_title(tr(ctx, "Export recipes"))
+                .set_file_name(format!("{}.{}", name, format.extension()))
+                .save_file();
+            execute(async move {
+                if let Some(file) = task.await {
+                    if let Err(err) = file.write(table.as_bytes()).await {
+                        error!("unable to write exported recipes: {}", err);
+                    }
+                }
+            });
+        }
+        Err(err) => error!("unable to export recipes: {}", err),
+    }
+}
+
+/// Darkens the window while files are dragged over it.
+fn preview_hovered_files(ctx: &egui::Context) {
+    if ctx.input(|input| input.raw.hovered_files.is_empty()) {
+        return;
+    }
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, Id::new("file_drop_target")));
+    let screen_rect = ctx.screen_rect();
+    painter.rect_filled(screen_rect, 0.0, Color32::from_black_alpha(192));
+    painter.text(
+        screen_rect.center(),
+        egui::Align2::CENTER_CENTER,
+        tr(ctx, "Drop a recipes.json, binary cache or project file"),
+        egui::TextStyle::Heading.resolve(&ctx.style()),
+        Color32::WHITE,
+    );
+}
+
+/// Temp data key of the ingredient under the mouse, for the R and U shortcuts
+const HOVERED_INGREDIENT: &str = "hovered_ingredient";
+
+/// Ingredient label that opens its recipes on left click and its uses on right click, like NEI.
+/// With ctrl held the lookup opens in a new tab.
+fn ingredient_label<T: IconName + IngredientText>(ui: &mut egui::Ui, icons: &Icons, ingredient: &T, name: String, lookup: &mut Option<(Lookup, bool)>) {
+    let text = ingredient.text(crate::i18n::names(ui.ctx()));
+    let response = ui
+        .horizontal(|ui| {
+            icons.icon_ui(ui, ingredient.icon_names());
+            ui.add(egui::Label::new(&text).sense(egui::Sense::click()))
+        })
+        .inner
+        .on_hover_text(tr(ui.ctx(), "Left click: recipes, right click: uses, ctrl: in a new tab"));
+    button_info(&response, &text);
+    if response.hovered() {
+        ui.ctx().data_mut(|data| data.insert_temp(Id::new(HOVERED_INGREDIENT), name.clone()));
+    }
+    let new_tab = ui.input(|input| input.modifiers.command);
+    if response.clicked() {
+        *lookup = Some((Lookup::Recipes(name), new_tab));
+    } else if response.secondary_clicked() {
+        *lookup = Some((Lookup::Uses(name), new_tab));
+    }
+}
+
 fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         ui.spacing_mut().item_spacing.x = 0.0;
@@ -423,12 +1208,12 @@ fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
     });
 }
 #[cfg(not(target_arch = "wasm32"))]
-fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
+pub(crate) fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
     // this is stupid... use any executor of your choice instead
     std::thread::spawn(move || futures::executor::block_on(f));
 }
 
 #[cfg(target_arch = "wasm32")]
-fn execute<F: Future<Output = ()> + 'static>(f: F) {
+pub(crate) fn execute<F: Future<Output = ()> + 'static>(f: F) {
     wasm_bindgen_futures::spawn_local(f);
 }
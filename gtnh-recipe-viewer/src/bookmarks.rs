@@ -0,0 +1,216 @@
+use std::collections::BTreeSet;
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::ingredients::NameDisplay;
+use gtnh_recipe_lib::types::recipe_id::RecipeId;
+use crate::i18n::tr;
+use crate::results_table::ingredient_list;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+struct BookmarkedRecipe {
+    id: RecipeId,
+    machine_name: String,
+    recipe: GregtechRecipe,
+    tags: BTreeSet<String>,
+}
+
+impl BookmarkedRecipe {
+    fn label(&self, names: NameDisplay) -> String {
+        let outputs = [ingredient_list(&self.recipe.item_outputs, names), ingredient_list(&self.recipe.fluid_outputs, names)]
+            .into_iter()
+            .filter(|list| !list.is_empty())
+            .collect::<Vec<String>>()
+            .join(" + ");
+        format!("{}: {}", self.machine_name, outputs)
+    }
+}
+
+pub enum BookmarkAction {
+    OpenRecipe(String, GregtechRecipe),
+    ShowMachine(String),
+}
+
+/// Starred recipes and machines with user defined tags, kept across restarts
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Bookmarks {
+    pub open: bool,
+    /// Recipes are matched by `RecipeId`, so bookmarks survive reloading or updating the dump
+    recipes: Vec<BookmarkedRecipe>,
+    machines: BTreeSet<String>,
+    /// Only show recipes with this tag in the sidebar
+    tag_filter: Option<String>,
+}
+
+impl Bookmarks {
+    fn find(&self, machine_name: &str, recipe: &GregtechRecipe) -> Option<&BookmarkedRecipe> {
+        let id = recipe.id(machine_name);
+        self.recipes.iter().find(|bookmark| bookmark.id == id)
+    }
+
+    fn find_mut(&mut self, machine_name: &str, recipe: &GregtechRecipe) -> Option<&mut BookmarkedRecipe> {
+        let id = recipe.id(machine_name);
+        self.recipes.iter_mut().find(|bookmark| bookmark.id == id)
+    }
+
+    pub fn is_starred(&self, machine_name: &str, recipe: &GregtechRecipe) -> bool {
+        self.find(machine_name, recipe).is_some()
+    }
+
+    pub fn toggle(&mut self, machine_name: &str, recipe: &GregtechRecipe) {
+        let id = recipe.id(machine_name);
+        if self.recipes.iter().any(|bookmark| bookmark.id == id) {
+            self.recipes.retain(|bookmark| bookmark.id != id);
+        } else {
+            self.recipes.push(BookmarkedRecipe {
+                id,
+                machine_name: machine_name.to_string(),
+                recipe: recipe.clone(),
+                tags: Default::default(),
+            });
+        }
+    }
+
+    pub fn is_machine_starred(&self, machine_name: &str) -> bool {
+        self.machines.contains(machine_name)
+    }
+
+    pub fn toggle_machine(&mut self, machine_name: &str) {
+        if !self.machines.remove(machine_name) {
+            self.machines.insert(machine_name.to_string());
+        }
+    }
+
+    /// Adds `tag` to a recipe, starring it if necessary.
+    pub fn add_tag(&mut self, machine_name: &str, recipe: &GregtechRecipe, tag: &str) {
+        if !self.is_starred(machine_name, recipe) {
+            self.toggle(machine_name, recipe);
+        }
+        if let Some(bookmark) = self.find_mut(machine_name, recipe) {
+            bookmark.tags.insert(tag.trim().to_string());
+        }
+    }
+
+    pub fn remove_tag(&mut self, machine_name: &str, recipe: &GregtechRecipe, tag: &str) {
+        if let Some(bookmark) = self.find_mut(machine_name, recipe) {
+            bookmark.tags.remove(tag);
+        }
+    }
+
+    pub fn tags(&self) -> BTreeSet<String> {
+        self.recipes.iter().flat_map(|bookmark| bookmark.tags.iter().cloned()).collect()
+    }
+
+    /// Star button and tag editor for the details window of a recipe.
+    pub fn recipe_ui(&mut self, ui: &mut egui::Ui, machine_name: &str, recipe: &GregtechRecipe) {
+        ui.horizontal_wrapped(|ui| {
+            let starred = self.is_starred(machine_name, recipe);
+            if ui.selectable_label(starred, if starred { "★" } else { "☆" }).on_hover_text(tr(ui.ctx(), "Favourite")).clicked() {
+                self.toggle(machine_name, recipe);
+            }
+
+            let tags = self
+                .find(machine_name, recipe)
+                .map(|bookmark| bookmark.tags.clone())
+                .unwrap_or_default();
+            for tag in tags {
+                if ui.small_button(format!("{} ✖", tag)).on_hover_text(tr(ui.ctx(), "Remove tag")).clicked() {
+                    self.remove_tag(machine_name, recipe, &tag);
+                }
+            }
+
+            let id = ui.id().with("new_tag");
+            let mut new_tag = ui.data_mut(|data| data.get_temp::<String>(id)).unwrap_or_default();
+            let response = ui.add(egui::TextEdit::singleline(&mut new_tag).hint_text(tr(ui.ctx(), "Add tag")).desired_width(80.0));
+            if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) && !new_tag.trim().is_empty() {
+                self.add_tag(machine_name, recipe, &new_tag);
+                new_tag.clear();
+            }
+            ui.data_mut(|data| data.insert_temp(id, new_tag));
+        });
+    }
+
+    pub fn sidebar_ui(&mut self, ui: &mut egui::Ui) -> Option<BookmarkAction> {
+        let mut action = None;
+
+        ui.strong(tr(ui.ctx(), "Machines"));
+        if self.machines.is_empty() {
+            ui.weak(tr(ui.ctx(), "Star machines in the recipe details"));
+        }
+        let mut unstarred = None;
+        for machine in &self.machines {
+            ui.horizontal(|ui| {
+                if ui.small_button("★").on_hover_text(tr(ui.ctx(), "Remove favourite")).clicked() {
+                    unstarred = Some(machine.clone());
+                }
+                if ui.link(machine).clicked() {
+                    action = Some(BookmarkAction::ShowMachine(machine.clone()));
+                }
+            });
+        }
+        if let Some(machine) = unstarred {
+            self.machines.remove(&machine);
+        }
+
+        ui.separator();
+        ui.strong(tr(ui.ctx(), "Recipes"));
+        let tags = self.tags();
+        if !tags.is_empty() {
+            egui::ComboBox::from_id_source("bookmark_tag_filter")
+                .selected_text(self.tag_filter.clone().unwrap_or_else(|| tr(ui.ctx(), "All tags").to_string()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.tag_filter, None, tr(ui.ctx(), "All tags"));
+                    for tag in tags {
+                        ui.selectable_value(&mut self.tag_filter, Some(tag.clone()), tag);
+                    }
+                });
+        }
+        if self.recipes.is_empty() {
+            ui.weak(tr(ui.ctx(), "Star recipes in the results or details windows"));
+        }
+        let names = crate::i18n::names(ui.ctx());
+        let mut removed = None;
+        for (index, bookmark) in self.recipes.iter().enumerate() {
+            if self.tag_filter.as_ref().is_some_and(|tag| !bookmark.tags.contains(tag)) {
+                continue;
+            }
+            ui.horizontal(|ui| {
+                if ui.small_button("★").on_hover_text(tr(ui.ctx(), "Remove favourite")).clicked() {
+                    removed = Some(index);
+                }
+                if ui.link(bookmark.label(names)).on_hover_text(bookmark.recipe.to_string()).clicked() {
+                    action = Some(BookmarkAction::OpenRecipe(bookmark.machine_name.clone(), bookmark.recipe.clone()));
+                }
+            });
+            if !bookmark.tags.is_empty() {
+                ui.small(bookmark.tags.iter().cloned().collect::<Vec<String>>().join(", "));
+            }
+        }
+        if let Some(index) = removed {
+            self.recipes.remove(index);
+        }
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_tags() {
+        let recipe = RecipeBuilder::new(30, 20).build();
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.add_tag("Mixer", &recipe, "platline");
+        bookmarks.add_tag("Mixer", &recipe, "early HV ");
+        assert!(bookmarks.is_starred("Mixer", &recipe));
+        // Recipes differing in EU/t only are different bookmarks
+        assert!(!bookmarks.is_starred("Mixer", &RecipeBuilder::new(120, 20).build()));
+        assert_eq!(bookmarks.tags().into_iter().collect::<Vec<String>>(), ["early HV", "platline"]);
+
+        bookmarks.remove_tag("Mixer", &recipe, "platline");
+        assert_eq!(bookmarks.tags().len(), 1);
+        bookmarks.toggle("Mixer", &recipe);
+        assert!(bookmarks.tags().is_empty());
+    }
+}
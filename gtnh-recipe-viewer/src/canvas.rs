@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+use gtnh_recipe_lib::export::graph::{EdgeDirection, IngredientKind, RecipeGraph};
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use crate::accessibility::{input_color, output_color};
+use crate::i18n::tr;
+
+/// Horizontal distance between a recipe and its ingredients when placing new nodes
+const COLUMN_SPACING: f32 = 240.0;
+const ROW_SPACING: f32 = 36.0;
+/// Number of recipes offered when expanding an ingredient
+const MAX_PRODUCERS: usize = 30;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+struct CanvasRecipe {
+    machine_name: String,
+    recipe: GregtechRecipe,
+    position: [f32; 2],
+}
+
+enum CanvasAction {
+    AddProducer(String, String, GregtechRecipe),
+    Collapse(String),
+    Remove(usize),
+}
+
+/// Zoomable, pannable flowchart of recipes and the ingredients connecting them
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct Canvas {
+    pub open: bool,
+    recipes: Vec<CanvasRecipe>,
+    /// Canvas position of every ingredient node, keyed by `ingredient_key`
+    ingredient_positions: HashMap<String, [f32; 2]>,
+    offset: [f32; 2],
+    zoom: f32,
+
+    /// Recipes offered by "Add producer", keyed by `ingredient_key`
+    #[serde(skip)]
+    producers: HashMap<String, Vec<(String, GregtechRecipe)>>,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self {
+            open: false,
+            recipes: vec![],
+            ingredient_positions: Default::default(),
+            offset: [0.0, 0.0],
+            zoom: 1.0,
+            producers: Default::default(),
+        }
+    }
+}
+
+fn ingredient_key(kind: IngredientKind, name: &str) -> String {
+    format!("{:?}:{}", kind, name)
+}
+
+impl Canvas {
+    /// Looks the producers up again, e.g. after loading another dump.
+    pub fn invalidate(&mut self) {
+        self.producers.clear();
+    }
+
+    /// Adds recipes that are not on the canvas yet below the existing ones.
+    pub fn add_recipes(&mut self, recipes: &[(String, GregtechRecipe)]) {
+        for (machine_name, recipe) in recipes {
+            let bottom = self
+                .recipes
+                .iter()
+                .map(|canvas_recipe| canvas_recipe.position[1])
+                .fold(None, |bottom: Option<f32>, y| Some(bottom.map_or(y, |bottom| bottom.max(y))));
+            let position = Pos2::new(0.0, bottom.map_or(0.0, |bottom| bottom + 4.0 * ROW_SPACING));
+            self.add_recipe_at(machine_name, recipe, position);
+        }
+        self.open = true;
+    }
+
+    fn add_recipe_at(&mut self, machine_name: &str, recipe: &GregtechRecipe, position: Pos2) {
+        if self.recipes.iter().any(|canvas_recipe| canvas_recipe.machine_name == machine_name && &canvas_recipe.recipe == recipe) {
+            return;
+        }
+        let inputs = recipe.item_inputs.iter().map(|item| ingredient_key(IngredientKind::Item, &item.get_name()))
+            .chain(recipe.fluid_inputs.iter().map(|fluid| ingredient_key(IngredientKind::Fluid, &fluid.get_name())))
+            .collect::<Vec<String>>();
+        let outputs = recipe.item_outputs.iter().map(|item| ingredient_key(IngredientKind::Item, &item.get_name()))
+            .chain(recipe.fluid_outputs.iter().map(|fluid| ingredient_key(IngredientKind::Fluid, &fluid.get_name())))
+            .collect::<Vec<String>>();
+        self.place_column(&inputs, position.x - COLUMN_SPACING, position.y);
+        self.place_column(&outputs, position.x + COLUMN_SPACING, position.y);
+
+        self.recipes.push(CanvasRecipe {
+            machine_name: machine_name.to_string(),
+            recipe: recipe.clone(),
+            position: position.into(),
+        });
+    }
+
+    /// Places ingredient nodes without a position in a column centered at `y`.
+    fn place_column(&mut self, keys: &[String], x: f32, y: f32) {
+        let top = y - (keys.len() as f32 - 1.0) * ROW_SPACING / 2.0;
+        for (row, key) in keys.iter().enumerate() {
+            self.ingredient_positions
+                .entry(key.clone())
+                .or_insert([x, top + row as f32 * ROW_SPACING]);
+        }
+    }
+
+    /// Removes the recipes producing `name`, and upstream recipes whose outputs are not used anymore.
+    fn collapse(&mut self, name: &str) {
+        let mut removed = vec![false; self.recipes.len()];
+        let is_used = |removed: &[bool], ingredient: &str| {
+            self.recipes.iter().enumerate()
+                .any(|(index, canvas_recipe)| !removed[index] && canvas_recipe.recipe.uses(ingredient))
+        };
+        let mut pending = vec![name.to_string()];
+        while let Some(ingredient) = pending.pop() {
+            for (index, canvas_recipe) in self.recipes.iter().enumerate() {
+                let recipe = &canvas_recipe.recipe;
+                if removed[index] || !recipe.produces(&ingredient) {
+                    continue;
+                }
+                let outputs_used = recipe.item_outputs.iter().map(|item| item.get_name())
+                    .chain(recipe.fluid_outputs.iter().map(|fluid| fluid.get_name()))
+                    .any(|output| is_used(&removed, &output));
+                if ingredient != name && outputs_used {
+                    continue;
+                }
+                removed[index] = true;
+                let inputs = recipe.item_inputs.iter().map(|item| item.get_name())
+                    .chain(recipe.fluid_inputs.iter().map(|fluid| fluid.get_name()));
+                pending.extend(inputs.filter(|input| !is_used(&removed, input)));
+            }
+        }
+        let mut removed = removed.into_iter();
+        self.recipes.retain(|_| !removed.next().unwrap_or(false));
+        self.forget_unused_ingredients();
+    }
+
+    /// Drops positions of ingredients no recipe on the canvas refers to anymore.
+    fn forget_unused_ingredients(&mut self) {
+        let graph = self.graph();
+        let used = graph.ingredients.iter()
+            .map(|ingredient| ingredient_key(ingredient.kind, &ingredient.name))
+            .collect::<Vec<String>>();
+        self.ingredient_positions.retain(|key, _| used.contains(key));
+        self.producers.retain(|key, _| used.contains(key));
+    }
+
+    fn graph(&self) -> RecipeGraph {
+        let mut graph = RecipeGraph::default();
+        for canvas_recipe in &self.recipes {
+            graph.add_recipe(&canvas_recipe.machine_name, &canvas_recipe.recipe, None);
+        }
+        graph
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context, recipes_json: Option<&JsonFormat>, selection: &[(String, GregtechRecipe)]) {
+        let mut open = self.open;
+        egui::Window::new(tr(ctx, "Recipe canvas"))
+            .open(&mut open)
+            .default_size([800.0, 600.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!selection.is_empty(), egui::Button::new(tr(ui.ctx(), "Add selection"))).clicked() {
+                        self.add_recipes(selection);
+                    }
+                    if ui.button(tr(ui.ctx(), "Reset view")).clicked() {
+                        self.offset = [0.0, 0.0];
+                        self.zoom = 1.0;
+                    }
+                    if ui.button(tr(ui.ctx(), "Clear")).clicked() {
+                        self.recipes.clear();
+                        self.ingredient_positions.clear();
+                        self.producers.clear();
+                    }
+                    ui.weak(tr(ui.ctx(), "Drag to pan, ctrl + scroll to zoom, right click nodes to expand or collapse"));
+                });
+                ui.separator();
+                self.canvas_ui(ui, recipes_json);
+            });
+        self.open = open;
+    }
+
+    fn canvas_ui(&mut self, ui: &mut egui::Ui, recipes_json: Option<&JsonFormat>) {
+        let (background, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let canvas_rect = background.rect;
+
+        if background.dragged() {
+            self.offset[0] += background.drag_delta().x;
+            self.offset[1] += background.drag_delta().y;
+        }
+        if background.hovered() {
+            let (zoom_delta, scroll_delta) = ui.input(|input| (input.zoom_delta(), input.smooth_scroll_delta));
+            if zoom_delta != 1.0 {
+                // Zoom around the mouse pointer
+                if let Some(pointer) = background.hover_pos() {
+                    let anchor = pointer - canvas_rect.center();
+                    let new_zoom = (self.zoom * zoom_delta).clamp(0.2, 4.0);
+                    let factor = new_zoom / self.zoom;
+                    self.offset[0] = anchor.x - (anchor.x - self.offset[0]) * factor;
+                    self.offset[1] = anchor.y - (anchor.y - self.offset[1]) * factor;
+                    self.zoom = new_zoom;
+                }
+            } else {
+                self.offset[0] += scroll_delta.x;
+                self.offset[1] += scroll_delta.y;
+            }
+        }
+
+        let painter = painter.with_clip_rect(canvas_rect);
+        let zoom = self.zoom;
+        let origin = canvas_rect.center() + Vec2::from(self.offset);
+        let to_screen = |position: [f32; 2]| origin + Vec2::from(position) * zoom;
+        let font = FontId::proportional(13.0 * zoom);
+        let text_color = ui.visuals().text_color();
+
+        let graph = self.graph();
+        let ingredient_rects = graph.ingredients.iter().map(|ingredient| {
+            let position = self.ingredient_positions.get(&ingredient_key(ingredient.kind, &ingredient.name)).copied().unwrap_or_default();
+            let galley = painter.layout_no_wrap(ingredient.name.clone(), font.clone(), text_color);
+            Rect::from_center_size(to_screen(position), galley.size() + Vec2::splat(8.0 * zoom))
+        }).collect::<Vec<Rect>>();
+        let recipe_rects = self.recipes.iter().zip(&graph.recipes).map(|(canvas_recipe, node)| {
+            let galley = painter.layout_no_wrap(node.machine_name.clone(), font.clone(), text_color);
+            let size = Vec2::new(galley.size().x.max(120.0 * zoom), galley.size().y * 2.0) + Vec2::splat(10.0 * zoom);
+            Rect::from_center_size(to_screen(canvas_recipe.position), size)
+        }).collect::<Vec<Rect>>();
+
+        // Edges below the nodes
+        for edge in &graph.edges {
+            let (ingredient, recipe) = (ingredient_rects[edge.ingredient], recipe_rects[edge.recipe]);
+            let (from, to, color) = match edge.direction {
+                EdgeDirection::Input => (ingredient.right_center(), recipe.left_center(), input_color(ui)),
+                EdgeDirection::Output => (recipe.right_center(), ingredient.left_center(), output_color(ui)),
+            };
+            painter.arrow(from, to - from, Stroke::new(1.5 * zoom, color));
+            let unit = match graph.ingredients[edge.ingredient].kind {
+                IngredientKind::Item => "x",
+                IngredientKind::Fluid => "l",
+            };
+            painter.text(from.lerp(to, 0.5), Align2::CENTER_BOTTOM, format!("{}{}", edge.amount, unit), FontId::proportional(11.0 * zoom), text_color);
+        }
+
+        let mut action = None;
+        for (index, (canvas_recipe, node)) in self.recipes.iter_mut().zip(&graph.recipes).enumerate() {
+            let rect = recipe_rects[index];
+            let response = ui.interact(rect, ui.id().with(("canvas_recipe", index)), Sense::click_and_drag());
+            if response.dragged() {
+                canvas_recipe.position[0] += response.drag_delta().x / zoom;
+                canvas_recipe.position[1] += response.drag_delta().y / zoom;
+            }
+            painter.rect(rect, 4.0 * zoom, ui.visuals().faint_bg_color, ui.visuals().widgets.inactive.fg_stroke);
+            painter.text(rect.center_top() + Vec2::new(0.0, 5.0 * zoom), Align2::CENTER_TOP, &node.machine_name, font.clone(), text_color);
+            painter.text(rect.center_bottom() - Vec2::new(0.0, 5.0 * zoom), Align2::CENTER_BOTTOM, format!("{}EU/t, {}s", node.eut, node.duration as f64 / 20.0), FontId::proportional(11.0 * zoom), text_color);
+            response.context_menu(|ui| {
+                if ui.button(tr(ui.ctx(), "Remove")).clicked() {
+                    action = Some(CanvasAction::Remove(index));
+                    ui.close_menu();
+                }
+            });
+        }
+
+        for (index, ingredient) in graph.ingredients.iter().enumerate() {
+            let rect = ingredient_rects[index];
+            let key = ingredient_key(ingredient.kind, &ingredient.name);
+            let response = ui.interact(rect, ui.id().with(("canvas_ingredient", &key)), Sense::click_and_drag());
+            if response.dragged() {
+                if let Some(position) = self.ingredient_positions.get_mut(&key) {
+                    position[0] += response.drag_delta().x / zoom;
+                    position[1] += response.drag_delta().y / zoom;
+                }
+            }
+            let fill = match ingredient.kind {
+                IngredientKind::Item => ui.visuals().extreme_bg_color,
+                IngredientKind::Fluid => Color32::from_rgb(40, 80, 140),
+            };
+            painter.rect(rect, 10.0 * zoom, fill, ui.visuals().widgets.inactive.fg_stroke);
+            painter.text(rect.center(), Align2::CENTER_CENTER, &ingredient.name, font.clone(), text_color);
+
+            response.context_menu(|ui| {
+                match recipes_json {
+                    Some(recipes_json) => {
+                        ui.menu_button(tr(ui.ctx(), "Add producer"), |ui| {
+                            let producers = self.producers.entry(key.clone()).or_insert_with(|| {
+                                let mut producers = recipes_json.recipes_producing(&ingredient.name);
+                                producers.truncate(MAX_PRODUCERS);
+                                producers
+                            });
+                            if producers.is_empty() {
+                                ui.label(tr(ui.ctx(), "No recipes found"));
+                            }
+                            for (machine_name, recipe) in producers.iter() {
+                                if ui.button(format!("{}: {}", machine_name, recipe)).clicked() {
+                                    action = Some(CanvasAction::AddProducer(key.clone(), machine_name.clone(), recipe.clone()));
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    }
+                    None => {
+                        ui.label(tr(ui.ctx(), "No recipes loaded"));
+                    }
+                }
+                let has_producer = self.recipes.iter().any(|canvas_recipe| canvas_recipe.recipe.produces(&ingredient.name));
+                if ui.add_enabled(has_producer, egui::Button::new(tr(ui.ctx(), "Collapse producers"))).clicked() {
+                    action = Some(CanvasAction::Collapse(ingredient.name.clone()));
+                    ui.close_menu();
+                }
+            });
+        }
+
+        match action {
+            Some(CanvasAction::AddProducer(key, machine_name, recipe)) => {
+                let position = self.ingredient_positions.get(&key).copied().unwrap_or_default();
+                self.add_recipe_at(&machine_name, &recipe, Pos2::new(position[0] - COLUMN_SPACING, position[1]));
+            }
+            Some(CanvasAction::Collapse(name)) => self.collapse(&name),
+            Some(CanvasAction::Remove(index)) => {
+                self.recipes.remove(index);
+                self.forget_unused_ingredients();
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_collapse() {
+        let mut canvas = Canvas::default();
+        canvas.add_recipes(&[("Mixer".to_string(), RecipeBuilder::new(30, 20).fluid_inputs(&[("A", 1000), ("B", 1000)]).fluid_outputs(&[("C", 1000)]).build())]);
+        let position = Pos2::new(-COLUMN_SPACING, 0.0);
+        canvas.add_recipe_at("Distillery", &RecipeBuilder::new(30, 20).fluid_inputs(&[("D", 1000)]).fluid_outputs(&[("A", 1000)]).build(), position);
+        canvas.add_recipe_at("Pump", &RecipeBuilder::new(30, 20).fluid_outputs(&[("D", 1000)]).build(), position);
+        // Also produces "D", but feeds the mixer directly
+        canvas.add_recipe_at("Extractor", &RecipeBuilder::new(30, 20).fluid_outputs(&[("B", 1000), ("D", 1000)]).build(), position);
+        assert_eq!(canvas.recipes.len(), 4);
+        assert_eq!(canvas.ingredient_positions.len(), 4);
+
+        canvas.collapse("A");
+        let machines = canvas.recipes.iter().map(|canvas_recipe| canvas_recipe.machine_name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(machines, ["Mixer", "Extractor"]);
+        assert_eq!(canvas.ingredient_positions.len(), 4);
+
+        canvas.collapse("C");
+        assert!(canvas.recipes.is_empty());
+        assert!(canvas.ingredient_positions.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use crate::i18n::tr;
+
+/// Row of the comparison grid, one value per recipe or `None` if the recipe lacks it
+#[derive(Debug, PartialEq)]
+struct ComparisonRow {
+    label: String,
+    values: Vec<Option<String>>,
+}
+
+impl ComparisonRow {
+    fn differs(&self) -> bool {
+        self.values.windows(2).any(|pair| pair[0] != pair[1])
+    }
+}
+
+/// Rows for the ingredients of all recipes, each ingredient once in order of appearance.
+fn ingredient_rows(recipes: &[(String, GregtechRecipe)], ingredients: impl Fn(&GregtechRecipe) -> Vec<(String, String)>) -> Vec<ComparisonRow> {
+    let mut rows: Vec<ComparisonRow> = vec![];
+    for (index, (_, recipe)) in recipes.iter().enumerate() {
+        for (name, amount) in ingredients(recipe) {
+            let position = match rows.iter().position(|row| row.label == name) {
+                Some(position) => position,
+                None => {
+                    rows.push(ComparisonRow { label: name, values: vec![None; recipes.len()] });
+                    rows.len() - 1
+                }
+            };
+            rows[position].values[index] = Some(amount);
+        }
+    }
+    rows
+}
+
+/// Grid sections "Recipe", "Inputs" and "Outputs" comparing `recipes`.
+fn comparison_sections(ctx: &egui::Context, recipes: &[(String, GregtechRecipe)]) -> Vec<(&'static str, Vec<ComparisonRow>)> {
+    let row = |label: &'static str, value: fn(&GregtechRecipe) -> String| ComparisonRow {
+        label: tr(ctx, label).to_string(),
+        values: recipes.iter().map(|(_, recipe)| Some(value(recipe))).collect(),
+    };
+    let recipe_rows = vec![
+        ComparisonRow {
+            label: tr(ctx, "Machine").to_string(),
+            values: recipes.iter().map(|(machine_name, _)| Some(machine_name.clone())).collect(),
+        },
+        row("EU/t", |recipe| recipe.eut.to_string()),
+        row("Duration", |recipe| format!("{}s", recipe.duration as f64 / 20.0)),
+        row("Total EU", |recipe| (recipe.eut * recipe.duration).to_string()),
+    ];
+    let inputs = ingredient_rows(recipes, |recipe| {
+        recipe.item_inputs.iter().map(|item| (item.get_name(), format!("{}x", item.amount)))
+            .chain(recipe.fluid_inputs.iter().map(|fluid| (fluid.get_name(), format!("{}l", fluid.amount))))
+            .collect()
+    });
+    let outputs = ingredient_rows(recipes, |recipe| {
+        recipe.item_outputs.iter().map(|item| (item.get_name(), format!("{}x", item.amount)))
+            .chain(recipe.fluid_outputs.iter().map(|fluid| (fluid.get_name(), format!("{}l", fluid.amount))))
+            .collect()
+    });
+    vec![("Recipe", recipe_rows), ("Inputs", inputs), ("Outputs", outputs)]
+}
+
+/// Recipes laid out side by side, e.g. alternative routes to the same product
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Comparison {
+    pub open: bool,
+    recipes: Vec<(String, GregtechRecipe)>,
+}
+
+impl Comparison {
+    pub fn add_recipes(&mut self, recipes: &[(String, GregtechRecipe)]) {
+        for (machine_name, recipe) in recipes {
+            let id = recipe.id(machine_name);
+            if !self.recipes.iter().any(|(machine_name, recipe)| recipe.id(machine_name) == id) {
+                self.recipes.push((machine_name.clone(), recipe.clone()));
+            }
+        }
+        self.open = true;
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new(tr(ctx, "Compare recipes"))
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                if self.recipes.len() < 2 {
+                    ui.label(tr(ui.ctx(), "Add two or more recipes (right click a search result) to compare them."));
+                }
+                if self.recipes.is_empty() {
+                    return;
+                }
+                egui::ScrollArea::both().show(ui, |ui| self.grid_ui(ui));
+                if ui.button(tr(ui.ctx(), "Clear")).clicked() {
+                    self.recipes.clear();
+                }
+            });
+        self.open = open;
+    }
+
+    fn grid_ui(&mut self, ui: &mut egui::Ui) {
+        let mut removed = None;
+        egui::Grid::new("comparison_grid").striped(true).num_columns(self.recipes.len() + 1).show(ui, |ui| {
+            ui.label("");
+            for index in 0..self.recipes.len() {
+                if ui.small_button("✖").on_hover_text(tr(ui.ctx(), "Remove from comparison")).clicked() {
+                    removed = Some(index);
+                }
+            }
+            ui.end_row();
+
+            let highlight = ui.visuals().warn_fg_color;
+            for (section, rows) in comparison_sections(ui.ctx(), &self.recipes) {
+                ui.strong(tr(ui.ctx(), section));
+                ui.end_row();
+                for row in rows {
+                    let differs = row.differs();
+                    ui.label(&row.label);
+                    for value in row.values {
+                        match value {
+                            Some(value) if differs => {
+                                ui.label(egui::RichText::new(value).color(highlight));
+                            }
+                            Some(value) => {
+                                ui.label(value);
+                            }
+                            None => {
+                                ui.label(egui::RichText::new("—").color(highlight)).on_hover_text(tr(ui.ctx(), "Not part of this recipe"));
+                            }
+                        }
+                    }
+                    ui.end_row();
+                }
+            }
+        });
+        if let Some(index) = removed {
+            self.recipes.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_sections() {
+        let recipes = vec![
+            ("Mixer".to_string(), RecipeBuilder::new(30, 40).fluid_inputs(&[("Water", 1000), ("Oxygen", 500)]).build()),
+            ("Mixer".to_string(), RecipeBuilder::new(120, 40).fluid_inputs(&[("Water", 1000)]).build()),
+        ];
+        let sections = comparison_sections(&egui::Context::default(), &recipes);
+        let [(_, recipe_rows), (_, inputs), (_, outputs)] = &sections[..] else { panic!() };
+
+        assert!(!recipe_rows[0].differs());
+        assert!(recipe_rows[1].differs());
+        assert_eq!(recipe_rows[3].values, [Some("1200".to_string()), Some("4800".to_string())]);
+
+        assert_eq!(inputs.len(), 2);
+        assert!(!inputs[0].differs());
+        assert_eq!(inputs[1], ComparisonRow { label: "Oxygen".to_string(), values: vec![Some("500l".to_string()), None] });
+        assert!(inputs[1].differs());
+        assert!(outputs.is_empty());
+    }
+}
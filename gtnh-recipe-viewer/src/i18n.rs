@@ -0,0 +1,339 @@
+use std::fmt::Display;
+use egui::Id;
+use gtnh_recipe_lib::types::ingredients::fluid::Fluid;
+use gtnh_recipe_lib::types::ingredients::item::Item;
+use gtnh_recipe_lib::types::ingredients::NameDisplay;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Name of the language in the language itself
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// Pairs of English texts and their translation, English needs none
+    fn translations(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => &[],
+            Language::German => GERMAN,
+        }
+    }
+}
+
+/// Temp data key of the localization of the current frame, for [`tr`] and [`names`]
+const LOCALIZATION: &str = "localization";
+
+/// UI language and which ingredient names to show, kept with the app settings
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct Localization {
+    pub language: Language,
+    pub names: NameDisplay,
+}
+
+impl Localization {
+    /// Makes these settings the ones used by [`tr`] and [`names`], call once per frame before drawing.
+    pub fn apply(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(Id::new(LOCALIZATION), self));
+    }
+
+    /// Language and name menu, returns whether the ingredient names changed.
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.label(tr(ui.ctx(), "Language"));
+        for language in Language::ALL {
+            ui.radio_value(&mut self.language, language, language.name());
+        }
+        ui.separator();
+        ui.label(tr(ui.ctx(), "Item and fluid names"));
+        let names = self.names;
+        for (display, label) in [
+            (NameDisplay::Localized, "Localized names"),
+            (NameDisplay::Unlocalized, "Unlocalized names"),
+            (NameDisplay::Both, "Both"),
+        ] {
+            ui.radio_value(&mut self.names, display, tr(ui.ctx(), label));
+        }
+        self.apply(ui.ctx());
+        self.names != names
+    }
+}
+
+fn localization(ctx: &egui::Context) -> Localization {
+    ctx.data(|data| data.get_temp(Id::new(LOCALIZATION))).unwrap_or_default()
+}
+
+/// Item and fluid names chosen in the settings
+pub fn names(ctx: &egui::Context) -> NameDisplay {
+    localization(ctx).names
+}
+
+fn translate(language: Language, english: &'static str) -> &'static str {
+    language
+        .translations()
+        .iter()
+        .find(|(key, _)| *key == english)
+        .map(|(_, translation)| *translation)
+        .unwrap_or(english)
+}
+
+/// `english` in the current UI language, texts without translation stay English.
+pub fn tr(ctx: &egui::Context, english: &'static str) -> &'static str {
+    translate(localization(ctx).language, english)
+}
+
+/// Translates `english` and fills its `{}` placeholders with `args` in order.
+pub fn tr_args(ctx: &egui::Context, english: &'static str, args: &[&dyn Display]) -> String {
+    fill(tr(ctx, english), args)
+}
+
+fn fill(text: &str, args: &[&dyn Display]) -> String {
+    let mut parts = text.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for (index, part) in parts.enumerate() {
+        if let Some(arg) = args.get(index) {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+/// Ingredients shown with amount and name
+pub trait IngredientText {
+    fn text(&self, names: NameDisplay) -> String;
+}
+
+impl IngredientText for Item {
+    fn text(&self, names: NameDisplay) -> String {
+        self.display_with(names)
+    }
+}
+
+impl IngredientText for Fluid {
+    fn text(&self, names: NameDisplay) -> String {
+        self.display_with(names)
+    }
+}
+
+const GERMAN: &[(&str, &str)] = &[
+    // Menus and panels
+    ("File", "Datei"),
+    ("Open new file", "Neue Datei öffnen"),
+    ("Quit", "Beenden"),
+    ("Forget cached dumps", "Zwischengespeicherte Dumps löschen"),
+    ("Dumps are kept in the browser to open them on the next visit", "Dumps werden im Browser gespeichert, um sie beim nächsten Besuch zu öffnen"),
+    ("Project", "Projekt"),
+    ("Save", "Speichern"),
+    ("Open", "Öffnen"),
+    ("Delete project", "Projekt löschen"),
+    ("New", "Neu"),
+    ("Export to file…", "In Datei exportieren…"),
+    ("Import from file…", "Aus Datei importieren…"),
+    ("Export project", "Projekt exportieren"),
+    ("Import project", "Projekt importieren"),
+    ("Export", "Exportieren"),
+    ("Export recipes", "Rezepte exportieren"),
+    ("Search results as {}", "Suchergebnisse als {}"),
+    ("Selection as {}", "Auswahl als {}"),
+    ("Share selection", "Auswahl teilen"),
+    ("Share planner result", "Planerergebnis teilen"),
+    ("Icons", "Symbole"),
+    ("Keyboard", "Tastatur"),
+    ("Shortcuts…", "Tastenkürzel…"),
+    ("View", "Ansicht"),
+    ("Language", "Sprache"),
+    ("Item and fluid names", "Namen von Gegenständen und Flüssigkeiten"),
+    ("Localized names", "Lokalisierte Namen"),
+    ("Unlocalized names", "Interne Namen"),
+    ("Both", "Beide"),
+    ("Display settings…", "Darstellung…"),
+    ("Display settings", "Darstellung"),
+    ("Font size", "Schriftgröße"),
+    ("Theme", "Farbschema"),
+    ("Contrast", "Kontrast"),
+    ("High contrast", "Hoher Kontrast"),
+    ("Inputs and outputs", "Eingaben und Ausgaben"),
+    ("Red and green", "Rot und Grün"),
+    ("Orange and blue (colour-blind safe)", "Orange und Blau (für Farbenblinde geeignet)"),
+    ("Inputs", "Eingaben"),
+    ("Outputs", "Ausgaben"),
+    ("Favourites", "Favoriten"),
+    ("Machines", "Maschinen"),
+    ("Planner", "Planer"),
+    ("Canvas", "Zeichenfläche"),
+    ("Compare", "Vergleichen"),
+    ("Compare the selected recipes", "Ausgewählte Rezepte vergleichen"),
+    // Start screen and search
+    ("GTNH recipe viewer", "GTNH-Rezeptbetrachter"),
+    ("Select recipes.json please", "Bitte recipes.json auswählen"),
+    ("Loading recipes…", "Rezepte werden geladen…"),
+    ("Cancel", "Abbrechen"),
+    ("Loading failed: {}", "Laden fehlgeschlagen: {}"),
+    ("Open recipes.json", "recipes.json öffnen"),
+    ("Drop a recipes.json, binary cache or project file", "recipes.json, Binär-Cache oder Projektdatei hier ablegen"),
+    ("Search: ", "Suche: "),
+    ("Search", "Suchen"),
+    ("Search something!", "Suche etwas!"),
+    ("{} of {} results shown", "{} von {} Ergebnissen angezeigt"),
+    ("Total recipes loaded: {}, search results: {}", "Geladene Rezepte: {}, Suchergebnisse: {}"),
+    ("No recipes loaded!", "Keine Rezepte geladen!"),
+    ("New tab", "Neuer Tab"),
+    // Results and details
+    ("Row", "Zeile"),
+    ("Machine", "Maschine"),
+    ("Duration", "Dauer"),
+    ("Items (Input)", "Gegenstände (Eingabe)"),
+    ("Fluids (Input)", "Flüssigkeiten (Eingabe)"),
+    ("Items (Output)", "Gegenstände (Ausgabe)"),
+    ("Fluids (Output)", "Flüssigkeiten (Ausgabe)"),
+    ("Add favourite", "Als Favorit markieren"),
+    ("Remove favourite", "Favorit entfernen"),
+    ("Add to planner", "Zum Planer hinzufügen"),
+    ("Add to comparison", "Zum Vergleich hinzufügen"),
+    ("Recipe Details", "Rezeptdetails"),
+    ("★ machine", "★ Maschine"),
+    ("Favourite machine", "Lieblingsmaschine"),
+    ("Item Inputs:", "Gegenstände (Eingabe):"),
+    ("Item Outputs:", "Gegenstände (Ausgabe):"),
+    ("Fluid Inputs:", "Flüssigkeiten (Eingabe):"),
+    ("Fluid Outputs:", "Flüssigkeiten (Ausgabe):"),
+    ("Result {}: {}, {} EU/t, {}s, inputs: {}, outputs: {}", "Ergebnis {}: {}, {} EU/t, {}s, Eingaben: {}, Ausgaben: {}"),
+    ("Left click: recipes, right click: uses, ctrl: in a new tab", "Linksklick: Rezepte, Rechtsklick: Verwendungen, Strg: in neuem Tab"),
+    // Bill of materials
+    ("Bill of materials", "Materialliste"),
+    ("Open the bill of materials of a recipe from its details window or by right clicking a search result.", "Öffne die Materialliste eines Rezepts im Detailfenster oder per Rechtsklick auf ein Suchergebnis."),
+    ("Crafts:", "Durchläufe:"),
+    ("Energy:", "Energie:"),
+    ("Time:", "Zeit:"),
+    ("Every step run one after another on a single machine", "Alle Schritte nacheinander auf je einer Maschine"),
+    ("Raw materials", "Rohstoffe"),
+    ("Expand", "Aufschlüsseln"),
+    ("Make it from other recipes again", "Wieder aus anderen Rezepten herstellen"),
+    ("Not expanded because they are needed to make themselves: {}", "Nicht aufgeschlüsselt, da sie für ihre eigene Herstellung benötigt werden: {}"),
+    ("Stopped after {} steps, further ingredients are listed as raw", "Nach {} Schritten abgebrochen, weitere Zutaten sind als Rohstoffe aufgeführt"),
+    ("Byproducts", "Nebenprodukte"),
+    ("Steps", "Schritte"),
+    ("Cheapest", "Günstigstes"),
+    ("for {}", "für {}"),
+    ("Treat as raw", "Als Rohstoff behandeln"),
+    ("Stop expanding this ingredient", "Diese Zutat nicht weiter aufschlüsseln"),
+    ("Reset choices", "Auswahl zurücksetzen"),
+    // Actions and shortcuts
+    ("Command palette", "Befehlspalette"),
+    ("Focus search", "Suche fokussieren"),
+    ("Next result", "Nächstes Ergebnis"),
+    ("Previous result", "Vorheriges Ergebnis"),
+    ("Open or close details of the result", "Details des Ergebnisses öffnen oder schließen"),
+    ("Show recipes (R)", "Rezepte anzeigen (R)"),
+    ("Show uses (U)", "Verwendungen anzeigen (U)"),
+    ("Close windows", "Fenster schließen"),
+    ("Back", "Zurück"),
+    ("Forward", "Vorwärts"),
+    ("Close tab", "Tab schließen"),
+    ("Toggle favourites", "Favoriten ein-/ausblenden"),
+    ("Toggle machine list", "Maschinenliste ein-/ausblenden"),
+    ("Toggle planner", "Planer ein-/ausblenden"),
+    ("Toggle canvas", "Zeichenfläche ein-/ausblenden"),
+    ("Toggle comparison", "Vergleich ein-/ausblenden"),
+    ("Toggle bill of materials", "Materialliste ein-/ausblenden"),
+    ("Keyboard shortcuts", "Tastenkürzel"),
+    ("Press a key… (Esc to cancel)", "Taste drücken… (Esc zum Abbrechen)"),
+    ("Change", "Ändern"),
+    ("Remove shortcut", "Tastenkürzel entfernen"),
+    ("Reset to defaults", "Auf Standard zurücksetzen"),
+    // Lookups and tabs
+    ("Search \"{}\"", "Suche „{}“"),
+    ("Recipes for {}", "Rezepte für {}"),
+    ("Uses of {}", "Verwendungen von {}"),
+    ("Recipes of {}", "Rezepte von {}"),
+    ("Sort", "Sortieren"),
+    ("Filter", "Filtern"),
+    ("Machines: all", "Maschinen: alle"),
+    ("Machines: {}", "Maschinen: {}"),
+    ("Show all", "Alle anzeigen"),
+    ("Cache", "Cache"),
+    // Favourites and machine list
+    ("Favourite", "Favorit"),
+    ("Remove tag", "Schlagwort entfernen"),
+    ("Add tag", "Schlagwort hinzufügen"),
+    ("Star machines in the recipe details", "Markiere Maschinen in den Rezeptdetails mit einem Stern"),
+    ("Recipes", "Rezepte"),
+    ("All tags", "Alle Schlagwörter"),
+    ("Star recipes in the results or details windows", "Markiere Rezepte in den Ergebnissen oder Detailfenstern mit einem Stern"),
+    ("No recipes loaded", "Keine Rezepte geladen"),
+    ("Filter machines", "Maschinen filtern"),
+    // Planner
+    ("Add recipes from the search results (right click a row) to plan a production chain.", "Füge Rezepte aus den Suchergebnissen hinzu (Rechtsklick auf eine Zeile), um eine Produktionskette zu planen."),
+    ("Target:", "Ziel:"),
+    ("Rate:", "Rate:"),
+    ("Clear", "Leeren"),
+    ("Output this step runs for, other outputs are byproducts", "Ausgabe, für die dieser Schritt läuft, andere Ausgaben sind Nebenprodukte"),
+    ("Remove step", "Schritt entfernen"),
+    ("The chain contains a loop that could not be balanced!", "Die Kette enthält eine Schleife, die nicht ausgeglichen werden konnte!"),
+    ("Count", "Anzahl"),
+    ("Power: {} EU/t", "Leistung: {} EU/t"),
+    ("None", "Keine"),
+    // Canvas
+    ("Recipe canvas", "Rezept-Zeichenfläche"),
+    ("Add selection", "Auswahl hinzufügen"),
+    ("Reset view", "Ansicht zurücksetzen"),
+    ("Drag to pan, ctrl + scroll to zoom, right click nodes to expand or collapse", "Ziehen zum Verschieben, Strg + Scrollen zum Zoomen, Rechtsklick auf Knoten zum Auf- oder Zuklappen"),
+    ("Remove", "Entfernen"),
+    ("Add producer", "Hersteller hinzufügen"),
+    ("No recipes found", "Keine Rezepte gefunden"),
+    ("Collapse producers", "Hersteller zuklappen"),
+    // Comparison
+    ("Compare recipes", "Rezepte vergleichen"),
+    ("Add two or more recipes (right click a search result) to compare them.", "Füge zwei oder mehr Rezepte hinzu (Rechtsklick auf ein Suchergebnis), um sie zu vergleichen."),
+    ("Remove from comparison", "Aus dem Vergleich entfernen"),
+    ("Recipe", "Rezept"),
+    ("Total EU", "EU gesamt"),
+    ("Not part of this recipe", "Nicht Teil dieses Rezepts"),
+    // Command palette
+    ("Type an action, machine or item", "Aktion, Maschine oder Gegenstand eingeben"),
+    ("Nothing found", "Nichts gefunden"),
+    ("Machine: {}", "Maschine: {}"),
+    // Icons
+    ("Load icon atlas…", "Symbolatlas laden…"),
+    ("Show icons", "Symbole anzeigen"),
+    ("Remove icon atlas", "Symbolatlas entfernen"),
+    ("{} icons loaded", "{} Symbole geladen"),
+    ("No icon atlas loaded", "Kein Symbolatlas geladen"),
+    ("Select the icon atlas image and mapping", "Bild und Zuordnung des Symbolatlas auswählen"),
+    // Sharing
+    ("Copy as {}", "Als {} kopieren"),
+    ("Save as {}…", "Als {} speichern…"),
+    ("text", "Text"),
+    ("SVG image", "SVG-Bild"),
+    ("Save as PNG image…", "Als PNG-Bild speichern…"),
+    ("Export cards", "Karten exportieren"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(Language::German, "Planner"), "Planer");
+        assert_eq!(translate(Language::English, "Planner"), "Planner");
+        assert_eq!(translate(Language::German, "not translated"), "not translated");
+        assert_eq!(fill(translate(Language::German, "{} of {} results shown"), &[&3, &10]), "3 von 10 Ergebnissen angezeigt");
+
+        for (index, (english, german)) in GERMAN.iter().enumerate() {
+            assert!(GERMAN[..index].iter().all(|(other, _)| other != english), "{} is translated twice", english);
+            assert_eq!(english.matches("{}").count(), german.matches("{}").count(), "placeholders of {}", english);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use gtnh_recipe_lib::types::ingredients::fluid::Fluid;
+use gtnh_recipe_lib::types::ingredients::item::Item;
+use crate::i18n::{tr, tr_args};
+
+/// Ingredients that can be looked up in the icon atlas
+pub trait IconName {
+    /// Names to look up, most specific first.
+    fn icon_names(&self) -> [Option<&str>; 2];
+}
+
+impl IconName for Item {
+    fn icon_names(&self) -> [Option<&str>; 2] {
+        [self.unlocalized_name.as_deref(), self.localized_name.as_deref()]
+    }
+}
+
+impl IconName for Fluid {
+    fn icon_names(&self) -> [Option<&str>; 2] {
+        [self.unlocalized_name.as_deref(), self.localized_name.as_deref()]
+    }
+}
+
+/// Sprite rectangles `[x, y, width, height]` in pixels, by lower case item or fluid name.
+///
+/// The mapping file is a JSON object from unlocalized or localized names to rectangles,
+/// e.g. `{"item.redstone": [0, 0, 32, 32], "Water": [32, 0, 32, 32]}`.
+fn parse_mapping(bytes: &[u8]) -> Result<HashMap<String, [u32; 4]>, serde_json::Error> {
+    let sprites: HashMap<String, [u32; 4]> = serde_json::from_slice(bytes)?;
+    Ok(sprites.into_iter().map(|(name, rect)| (name.to_lowercase(), rect)).collect())
+}
+
+/// Texture coordinates of a sprite in an atlas of `size` pixels.
+fn uv(rect: [u32; 4], size: [usize; 2]) -> egui::Rect {
+    let [x, y, width, height] = rect.map(|value| value as f32);
+    let [atlas_width, atlas_height] = size.map(|value| value as f32);
+    egui::Rect::from_min_max(
+        egui::pos2(x / atlas_width, y / atlas_height),
+        egui::pos2((x + width) / atlas_width, (y + height) / atlas_height),
+    )
+}
+
+/// Picked files by name and contents
+type PickedFiles = Vec<(String, Vec<u8>)>;
+
+struct IconAtlas {
+    texture: egui::TextureHandle,
+    sprites: HashMap<String, [u32; 4]>,
+}
+
+impl IconAtlas {
+    fn load(ctx: &egui::Context, image: &[u8], mapping: &[u8]) -> Result<Self, String> {
+        let sprites = parse_mapping(mapping).map_err(|err| format!("invalid icon mapping: {}", err))?;
+        let image = image::load_from_memory(image)
+            .map_err(|err| format!("invalid icon image: {}", err))?
+            .to_rgba8();
+        let size = [image.width() as usize, image.height() as usize];
+        let image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice());
+        // Nearest filtering keeps the pixel art sharp
+        let texture = ctx.load_texture("icon_atlas", image, egui::TextureOptions::NEAREST);
+        Ok(IconAtlas { texture, sprites })
+    }
+
+    fn sprite(&self, names: [Option<&str>; 2]) -> Option<egui::Image<'static>> {
+        let rect = names
+            .into_iter()
+            .flatten()
+            .find_map(|name| self.sprites.get(&name.to_lowercase()))?;
+        Some(egui::Image::new(egui::load::SizedTexture::from_handle(&self.texture)).uv(uv(*rect, self.texture.size())))
+    }
+}
+
+/// Item and fluid icons from a user supplied sprite sheet, text is shown for anything without an icon
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Icons {
+    pub show: bool,
+    /// Image and mapping file of the atlas, reloaded on start (native only)
+    files: Option<(PathBuf, PathBuf)>,
+
+    #[serde(skip)]
+    atlas: Option<IconAtlas>,
+    #[serde(skip)]
+    error: Option<String>,
+    #[serde(skip)]
+    channel: (Sender<PickedFiles>, Receiver<PickedFiles>),
+}
+
+impl Default for Icons {
+    fn default() -> Self {
+        Self {
+            show: true,
+            files: None,
+            atlas: None,
+            error: None,
+            channel: channel(),
+        }
+    }
+}
+
+impl Icons {
+    /// Loads picked atlas files and the atlas of the last session.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        if let Ok(files) = self.channel.1.try_recv() {
+            self.load_files(ctx, files);
+        }
+        if self.atlas.is_none() && self.error.is_none() {
+            if let Some((image, mapping)) = self.files.clone() {
+                match (std::fs::read(&image), std::fs::read(&mapping)) {
+                    (Ok(image), Ok(mapping)) => self.load(ctx, &image, &mapping),
+                    (Err(err), _) | (_, Err(err)) => {
+                        self.error = Some(format!("unable to read icon atlas: {}", err));
+                        self.files = None;
+                    }
+                }
+            }
+        }
+    }
+
+    fn load(&mut self, ctx: &egui::Context, image: &[u8], mapping: &[u8]) {
+        match IconAtlas::load(ctx, image, mapping) {
+            Ok(atlas) => {
+                self.atlas = Some(atlas);
+                self.error = None;
+            }
+            Err(err) => {
+                self.error = Some(err);
+                self.files = None;
+            }
+        }
+    }
+
+    /// Loads an atlas from a PNG and a JSON file, given by name and contents.
+    fn load_files(&mut self, ctx: &egui::Context, files: PickedFiles) {
+        let image = files.iter().find(|(name, _)| name.to_lowercase().ends_with(".png"));
+        let mapping = files.iter().find(|(name, _)| name.to_lowercase().ends_with(".json"));
+        match (image, mapping) {
+            (Some((image_name, image)), Some((mapping_name, mapping))) => {
+                self.load(ctx, image, mapping);
+                if self.atlas.is_some() && !cfg!(target_arch = "wasm32") {
+                    self.files = Some((PathBuf::from(image_name), PathBuf::from(mapping_name)));
+                }
+            }
+            _ => self.error = Some("select the atlas image (.png) and its mapping (.json)".to_string()),
+        }
+    }
+
+    fn pick_files(&self, ctx: &egui::Context) {
+        let sender = self.channel.0.clone();
+        let task = rfd::AsyncFileDialog::new()
+            .set_title(tr(ctx, "Select the icon atlas image and mapping"))
+            .add_filter("icon atlas", &["png", "json"])
+            .pick_files();
+        let ctx = ctx.clone();
+        crate::app::execute(async move {
+            if let Some(handles) = task.await {
+                let mut files = vec![];
+                for handle in handles {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let name = handle.path().to_string_lossy().to_string();
+                    #[cfg(target_arch = "wasm32")]
+                    let name = handle.file_name();
+                    files.push((name, handle.read().await));
+                }
+                let _ = sender.send(files);
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) {
+        if ui.button(tr(ui.ctx(), "Load icon atlas…")).clicked() {
+            self.pick_files(ui.ctx());
+            ui.close_menu();
+        }
+        ui.add_enabled(self.atlas.is_some(), egui::Checkbox::new(&mut self.show, tr(ui.ctx(), "Show icons")));
+        if ui.add_enabled(self.atlas.is_some(), egui::Button::new(tr(ui.ctx(), "Remove icon atlas"))).clicked() {
+            self.atlas = None;
+            self.files = None;
+            ui.close_menu();
+        }
+        match (&self.atlas, &self.error) {
+            (Some(atlas), _) => {
+                ui.weak(tr_args(ui.ctx(), "{} icons loaded", &[&atlas.sprites.len()]));
+            }
+            (None, Some(err)) => {
+                ui.colored_label(egui::Color32::RED, err);
+            }
+            (None, None) => {
+                ui.weak(tr(ui.ctx(), "No icon atlas loaded"));
+            }
+        }
+    }
+
+    /// Draws the icon of `names` at text height, returns whether there was one.
+    pub fn icon_ui(&self, ui: &mut egui::Ui, names: [Option<&str>; 2]) -> bool {
+        let Some(image) = self.atlas.as_ref().filter(|_| self.show).and_then(|atlas| atlas.sprite(names)) else {
+            return false;
+        };
+        let size = ui.text_style_height(&egui::TextStyle::Body);
+        ui.add(image.fit_to_exact_size(egui::vec2(size, size)));
+        true
+    }
+
+    /// Ingredient list for table cells, each ingredient with its icon.
+    pub fn ingredients_ui<T: IconName + crate::i18n::IngredientText>(&self, ui: &mut egui::Ui, ingredients: &[T]) {
+        if self.atlas.is_none() || !self.show {
+            ui.label(crate::results_table::ingredient_list(ingredients, crate::i18n::names(ui.ctx())));
+            return;
+        }
+        let names = crate::i18n::names(ui.ctx());
+        ui.spacing_mut().item_spacing.x = 2.0;
+        for (index, ingredient) in ingredients.iter().enumerate() {
+            if index > 0 {
+                ui.label("+");
+            }
+            self.icon_ui(ui, ingredient.icon_names());
+            ui.label(ingredient.text(names));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping() {
+        let sprites = parse_mapping(br#"{"item.redstone": [0, 0, 32, 32], "Water": [32, 16, 16, 16]}"#).unwrap();
+        assert_eq!(sprites["water"], [32, 16, 16, 16]);
+        assert!(parse_mapping(br#"{"Water": [32, 16]}"#).is_err());
+
+        let rect = uv(sprites["water"], [64, 32]);
+        assert_eq!(rect, egui::Rect::from_min_max(egui::pos2(0.5, 0.5), egui::pos2(0.75, 1.0)));
+    }
+}
@@ -1,4 +1,23 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+mod accessibility;
 mod app;
+mod bookmarks;
+mod canvas;
+mod comparison;
+mod i18n;
+mod icons;
+mod loader;
+mod machines;
+mod materials;
+mod navigation;
+mod palette;
+mod planner;
+mod project;
+mod results_table;
+mod share;
+mod shortcuts;
+mod tabs;
+#[cfg(target_arch = "wasm32")]
+mod web;
 pub use app::GtnhRecipeViewerApp;
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use gtnh_recipe_lib::load::{from_binary, is_binary, IncrementalLoader, LoadError};
+#[cfg(target_arch = "wasm32")]
+use gtnh_recipe_lib::load::to_binary;
+use gtnh_recipe_lib::types::json::JsonFormat;
+
+pub enum LoadSource {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+    /// Same-origin URL, downloaded again on every load, the saved copy is used when offline
+    #[cfg(target_arch = "wasm32")]
+    Url(String),
+    /// Binary cache of the dump loaded last, from IndexedDB
+    #[cfg(target_arch = "wasm32")]
+    LastOpened,
+}
+
+/// Recipe dump or binary cache being parsed off the UI thread
+pub struct BackgroundLoad {
+    /// Fraction of the dump parsed so far, as `f32` bits
+    progress: Arc<AtomicU32>,
+    cancel: Arc<AtomicBool>,
+    result: Receiver<Result<JsonFormat, LoadError>>,
+}
+
+impl BackgroundLoad {
+    pub fn start(source: LoadSource, ctx: &egui::Context) -> Self {
+        let progress = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (sender, result) = channel();
+        spawn_load(source, ctx.clone(), progress.clone(), cancel.clone(), sender);
+        BackgroundLoad { progress, cancel, result }
+    }
+
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.progress.load(Ordering::Relaxed))
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The loaded recipes or the error, once loading finished.
+    pub fn poll(&self) -> Option<Result<JsonFormat, LoadError>> {
+        self.result.try_recv().ok()
+    }
+}
+
+/// Parses on a worker thread.
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_load(
+    source: LoadSource,
+    ctx: egui::Context,
+    progress: Arc<AtomicU32>,
+    cancel: Arc<AtomicBool>,
+    sender: Sender<Result<JsonFormat, LoadError>>,
+) {
+    std::thread::spawn(move || {
+        let result = match source {
+            LoadSource::Path(path) => std::fs::read(path).map_err(LoadError::from),
+            LoadSource::Bytes(bytes) => Ok(bytes),
+        }
+        .and_then(|bytes| {
+            if is_binary(&bytes) {
+                return from_binary(&bytes);
+            }
+            IncrementalLoader::new(&bytes)?.run(|fraction| {
+                progress.store(fraction.to_bits(), Ordering::Relaxed);
+                ctx.request_repaint();
+                !cancel.load(Ordering::Relaxed)
+            })
+        });
+        let _ = sender.send(result);
+        ctx.request_repaint();
+    });
+}
+
+/// Parses one machine per task on the browser's event loop, so the page stays responsive.
+#[cfg(target_arch = "wasm32")]
+fn spawn_load(
+    source: LoadSource,
+    ctx: egui::Context,
+    progress: Arc<AtomicU32>,
+    cancel: Arc<AtomicBool>,
+    sender: Sender<Result<JsonFormat, LoadError>>,
+) {
+    wasm_bindgen_futures::spawn_local(async move {
+        // Info to keep the dump with for the next visit, unless it is the kept one
+        let mut save_as = None;
+        let result: Result<JsonFormat, LoadError> = async {
+            let dump = match source {
+                LoadSource::Path(_) => {
+                    return Err(LoadError::Io(std::io::Error::from(std::io::ErrorKind::Unsupported)))
+                }
+                LoadSource::Bytes(bytes) => crate::web::Dump { bytes, save_as: Some(crate::web::SavedDumpInfo::current(None)) },
+                LoadSource::Url(url) => crate::web::fetch_dump(&url).await.map_err(|err| LoadError::Io(std::io::Error::other(err)))?,
+                LoadSource::LastOpened => match crate::web::load_last_opened().await {
+                    Ok(Some(dump)) => dump,
+                    // Nothing cached, show the start screen like after cancelling
+                    Ok(None) => return Err(LoadError::Cancelled),
+                    Err(err) => return Err(LoadError::Io(std::io::Error::other(err))),
+                },
+            };
+            save_as = dump.save_as;
+            let bytes = dump.bytes;
+            // Let the progress bar render before checking the whole dump
+            gloo_timers::future::TimeoutFuture::new(0).await;
+            if is_binary(&bytes) {
+                return from_binary(&bytes);
+            }
+            let mut loader = IncrementalLoader::new(&bytes)?;
+            drop(bytes);
+            while !loader.is_finished() {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(LoadError::Cancelled);
+                }
+                loader.step()?;
+                progress.store(loader.progress().to_bits(), Ordering::Relaxed);
+                ctx.request_repaint();
+                gloo_timers::future::TimeoutFuture::new(0).await;
+            }
+            Ok(loader.finish())
+        }
+        .await;
+        let saved = match (&result, save_as) {
+            (Ok(recipes), Some(info)) => Some((to_binary(recipes), info)),
+            _ => None,
+        };
+        let _ = sender.send(result);
+        ctx.request_repaint();
+        match saved {
+            Some((Ok(bytes), info)) => {
+                if let Err(err) = crate::web::save_last_opened(bytes, info).await {
+                    log::error!("unable to save the recipe dump for offline use: {}", err);
+                }
+            }
+            Some((Err(err), _)) => log::error!("unable to encode the recipe dump for offline use: {}", err),
+            None => {}
+        }
+    });
+}
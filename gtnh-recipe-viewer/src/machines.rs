@@ -0,0 +1,47 @@
+use gtnh_recipe_lib::types::json::JsonFormat;
+use crate::i18n::tr;
+
+/// Sidebar listing every machine of the dump, like the category tabs of NEI
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct MachineList {
+    pub open: bool,
+    filter: String,
+
+    #[serde(skip)]
+    machines: Option<Vec<(String, usize)>>,
+}
+
+impl MachineList {
+    /// Forgets the machines of the previous dump.
+    pub fn invalidate(&mut self) {
+        self.machines = None;
+    }
+
+    /// Machine list, returns the machine the user clicked.
+    ///
+    /// `current` is highlighted, it is the machine whose recipes are shown.
+    pub fn sidebar_ui(&mut self, ui: &mut egui::Ui, recipes: Option<&JsonFormat>, current: Option<&str>) -> Option<String> {
+        let Some(recipes) = recipes else {
+            ui.weak(tr(ui.ctx(), "No recipes loaded"));
+            return None;
+        };
+        let machines = self.machines.get_or_insert_with(|| recipes.machine_recipe_counts());
+
+        ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text(tr(ui.ctx(), "Filter machines")));
+        ui.separator();
+
+        let filter = self.filter.trim().to_lowercase();
+        let mut clicked = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (name, count) in machines.iter().filter(|(name, _)| name.to_lowercase().contains(&filter)) {
+                let response = ui.selectable_label(current == Some(name.as_str()), format!("{} ({})", name, count));
+                if response.clicked() {
+                    clicked = Some(name.clone());
+                }
+            }
+        });
+        clicked
+    }
+}
+
@@ -1,15 +1,27 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(clap::Parser)]
+#[command(version, about = "Viewer for GTNH recipe dumps")]
+struct Cli {
+    /// Recipe dump exported by RecEx or binary cache to open on start
+    file: Option<std::path::PathBuf>,
+}
+
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result {
+    use clap::Parser;
+
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+    let cli = Cli::parse();
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
             .with_min_inner_size([300.0, 220.0])
+            .with_drag_and_drop(true)
             .with_icon(
                 // NOTE: Adding an icon is optional
                 eframe::icon_data::from_png_bytes(&include_bytes!("../assets/icon-256.png")[..])
@@ -20,7 +32,13 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "GTNH recipe viewer",
         native_options,
-        Box::new(|cc| Ok(Box::new(gtnh_recipe_viewer::GtnhRecipeViewerApp::new(cc)))),
+        Box::new(|cc| {
+            let mut app = gtnh_recipe_viewer::GtnhRecipeViewerApp::new(cc);
+            if let Some(file) = cli.file {
+                app.open_path(file);
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
 
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use gtnh_recipe_lib::types::bill_of_materials::{producers, BillOfMaterials, MaterialAmount, MAX_STEPS};
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::ingredients::IngredientKind;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use gtnh_recipe_lib::types::recipe_id::RecipeId;
+use crate::accessibility::{input_color, output_color};
+use crate::i18n::{tr, tr_args};
+
+/// Alternatives offered per ingredient, the list of a common item can be very long
+const MAX_PRODUCERS: usize = 30;
+
+fn amount_text(material: &MaterialAmount) -> String {
+    let unit = match material.kind {
+        IngredientKind::Item => "x",
+        IngredientKind::Fluid => "l",
+    };
+    format!("{}{} {}", format_number(material.amount), unit, material.name)
+}
+
+/// Whole numbers without decimals, others rounded to two.
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 { format!("{}", value) } else { format!("{:.2}", value) }
+}
+
+/// Raw materials of a recipe, with a picker for the recipe making each intermediate ingredient
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct MaterialsWindow {
+    pub open: bool,
+    recipe: Option<(String, GregtechRecipe)>,
+    crafts: f64,
+    /// Producer recipe picked per ingredient, others use the cheapest one
+    choices: BTreeMap<String, RecipeId>,
+    /// Ingredients not to expand, e.g. because they are farmed
+    raw: BTreeSet<String>,
+
+    #[serde(skip)]
+    bill: Option<BillOfMaterials>,
+    /// Recipes offered per ingredient in the producer pickers
+    #[serde(skip)]
+    producers: HashMap<String, Vec<(String, GregtechRecipe)>>,
+}
+
+impl Default for MaterialsWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            recipe: None,
+            crafts: 1.0,
+            choices: Default::default(),
+            raw: Default::default(),
+            bill: None,
+            producers: Default::default(),
+        }
+    }
+}
+
+impl MaterialsWindow {
+    pub fn show_recipe(&mut self, machine_name: &str, recipe: &GregtechRecipe) {
+        self.recipe = Some((machine_name.to_string(), recipe.clone()));
+        self.open = true;
+        self.bill = None;
+    }
+
+    /// Expands the recipe and looks the producers up again, e.g. after loading another dump.
+    pub fn invalidate(&mut self) {
+        self.bill = None;
+        self.producers.clear();
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context, recipes: Option<&JsonFormat>) {
+        let mut open = self.open;
+        egui::Window::new(tr(ctx, "Bill of materials"))
+            .open(&mut open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let (Some((machine_name, recipe)), Some(recipes)) = (&self.recipe, recipes) else {
+                    ui.label(tr(ui.ctx(), "Open the bill of materials of a recipe from its details window or by right clicking a search result."));
+                    return;
+                };
+                let bill = self.bill.get_or_insert_with(|| {
+                    BillOfMaterials::expand(recipes, machine_name, recipe, self.crafts, &self.choices, &self.raw)
+                });
+
+                ui.horizontal(|ui| {
+                    ui.heading(machine_name.as_str());
+                    ui.label(recipe.to_string());
+                });
+                let mut changed = false;
+                egui::Grid::new("materials_summary").num_columns(2).show(ui, |ui| {
+                    ui.label(tr(ui.ctx(), "Crafts:"));
+                    changed |= ui.add(egui::DragValue::new(&mut self.crafts).speed(1.0).range(1.0..=f64::MAX)).changed();
+                    ui.end_row();
+                    ui.label(tr(ui.ctx(), "Energy:"));
+                    ui.label(format!("{} EU", format_number(bill.eu.round())));
+                    ui.end_row();
+                    ui.label(tr(ui.ctx(), "Time:"));
+                    ui.label(format!("{}s", format_number(bill.ticks / 20.0)))
+                        .on_hover_text(tr(ui.ctx(), "Every step run one after another on a single machine"));
+                    ui.end_row();
+                });
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.separator();
+                    ui.label(egui::RichText::new(tr(ui.ctx(), "Raw materials")).strong().color(input_color(ui)));
+                    for material in &bill.raw {
+                        ui.horizontal(|ui| {
+                            ui.label(amount_text(material));
+                            if self.raw.contains(&material.name) && ui.small_button(tr(ui.ctx(), "Expand")).on_hover_text(tr(ui.ctx(), "Make it from other recipes again")).clicked() {
+                                self.raw.remove(&material.name);
+                                changed = true;
+                            }
+                        });
+                    }
+                    if !bill.cycles.is_empty() {
+                        ui.weak(tr_args(ui.ctx(), "Not expanded because they are needed to make themselves: {}", &[&bill.cycles.join(", ")]));
+                    }
+                    if bill.truncated {
+                        ui.weak(tr_args(ui.ctx(), "Stopped after {} steps, further ingredients are listed as raw", &[&MAX_STEPS]));
+                    }
+
+                    if !bill.byproducts.is_empty() {
+                        ui.separator();
+                        ui.label(egui::RichText::new(tr(ui.ctx(), "Byproducts")).strong().color(output_color(ui)));
+                        for material in &bill.byproducts {
+                            ui.label(amount_text(material));
+                        }
+                    }
+
+                    ui.separator();
+                    ui.strong(tr(ui.ctx(), "Steps"));
+                    for (index, step) in bill.steps.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add_space(16.0 * step.depth as f32);
+                            let Some(product) = &step.product else {
+                                ui.label(format!("{} × {}", format_number(step.crafts), step.machine_name));
+                                return;
+                            };
+                            ui.label(format!("{} × ", format_number(step.crafts)));
+                            egui::ComboBox::from_id_source(("materials_producer", index))
+                                .selected_text(step.machine_name.as_str())
+                                .show_ui(ui, |ui| {
+                                    if ui.selectable_label(!self.choices.contains_key(product), tr(ui.ctx(), "Cheapest")).clicked() {
+                                        changed |= self.choices.remove(product).is_some();
+                                    }
+                                    let producers = self.producers.entry(product.clone()).or_insert_with(|| {
+                                        let mut producers = producers(recipes, product);
+                                        producers.truncate(MAX_PRODUCERS);
+                                        producers
+                                    });
+                                    for (machine_name, recipe) in producers.iter() {
+                                        let id = recipe.id(machine_name);
+                                        let chosen = self.choices.get(product) == Some(&id);
+                                        if ui.selectable_label(chosen, format!("{}: {}", machine_name, recipe)).clicked() && !chosen {
+                                            self.choices.insert(product.clone(), id);
+                                            changed = true;
+                                        }
+                                    }
+                                });
+                            ui.label(tr_args(ui.ctx(), "for {}", &[product]));
+                            if ui.small_button(tr(ui.ctx(), "Treat as raw")).on_hover_text(tr(ui.ctx(), "Stop expanding this ingredient")).clicked() {
+                                self.raw.insert(product.clone());
+                                changed = true;
+                            }
+                        });
+                    }
+                });
+
+                ui.separator();
+                if ui.add_enabled(!self.choices.is_empty() || !self.raw.is_empty(), egui::Button::new(tr(ui.ctx(), "Reset choices"))).clicked() {
+                    self.choices.clear();
+                    self.raw.clear();
+                    changed = true;
+                }
+                if changed {
+                    self.bill = None;
+                }
+            });
+        self.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_text() {
+        let material = |kind, amount| MaterialAmount { name: "Water".to_string(), kind, amount };
+        assert_eq!(amount_text(&material(IngredientKind::Fluid, 1000.0)), "1000l Water");
+        assert_eq!(amount_text(&material(IngredientKind::Item, 2.0 / 3.0)), "0.67x Water");
+    }
+}
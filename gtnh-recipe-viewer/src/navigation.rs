@@ -0,0 +1,132 @@
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use crate::i18n::tr_args;
+
+/// What the results table currently shows, NEI style
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+pub enum Lookup {
+    Search(String),
+    /// Recipes producing an ingredient (NEI "R")
+    Recipes(String),
+    /// Recipes consuming an ingredient (NEI "U")
+    Uses(String),
+    /// All recipes of a machine
+    Machine(String),
+}
+
+impl Lookup {
+    pub fn run(&self, recipes: &JsonFormat) -> Vec<(String, GregtechRecipe)> {
+        match self {
+            Lookup::Search(keyword) => recipes.search(keyword),
+            Lookup::Recipes(name) => recipes.recipes_producing(name),
+            Lookup::Uses(name) => recipes.recipes_using(name),
+            Lookup::Machine(name) => recipes.recipes_of_machine(name),
+        }
+    }
+
+    /// Title of the lookup in the current UI language, e.g. for tabs.
+    pub fn title(&self, ctx: &egui::Context) -> String {
+        match self {
+            Lookup::Search(keyword) => tr_args(ctx, "Search \"{}\"", &[keyword]),
+            Lookup::Recipes(name) => tr_args(ctx, "Recipes for {}", &[name]),
+            Lookup::Uses(name) => tr_args(ctx, "Uses of {}", &[name]),
+            Lookup::Machine(name) => tr_args(ctx, "Recipes of {}", &[name]),
+        }
+    }
+}
+
+
+/// Entries kept per history, older ones are dropped as it is saved with every tab and project
+const MAX_ENTRIES: usize = 100;
+
+/// Browser-like back/forward history of lookups
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct History {
+    entries: Vec<Lookup>,
+    position: usize,
+}
+
+impl History {
+    pub fn current(&self) -> Option<&Lookup> {
+        self.entries.get(self.position)
+    }
+
+    /// Makes `lookup` the current entry, dropping everything after the current one and the oldest entries
+    /// beyond [`MAX_ENTRIES`].
+    pub fn push(&mut self, lookup: Lookup) {
+        if self.current() == Some(&lookup) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.position + 1);
+            self.position += 1;
+        }
+        self.entries.push(lookup);
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+            self.position -= excess;
+        }
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.position + 1 < self.entries.len()
+    }
+
+    pub fn back(&mut self) -> Option<&Lookup> {
+        if self.can_go_back() {
+            self.position -= 1;
+        }
+        self.current()
+    }
+
+    pub fn forward(&mut self) -> Option<&Lookup> {
+        if self.can_go_forward() {
+            self.position += 1;
+        }
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let mut history = History::default();
+        assert_eq!(history.current(), None);
+        history.push(Lookup::Search("cell".to_string()));
+        history.push(Lookup::Recipes("Empty Cell".to_string()));
+        history.push(Lookup::Recipes("Empty Cell".to_string()));
+        history.push(Lookup::Uses("Tin Plate".to_string()));
+
+        assert_eq!(history.back(), Some(&Lookup::Recipes("Empty Cell".to_string())));
+        assert_eq!(history.back(), Some(&Lookup::Search("cell".to_string())));
+        assert!(!history.can_go_back());
+        assert_eq!(history.forward(), Some(&Lookup::Recipes("Empty Cell".to_string())));
+
+        // a new lookup discards the forward entries
+        history.push(Lookup::Uses("Empty Cell".to_string()));
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(), Some(&Lookup::Recipes("Empty Cell".to_string())));
+    }
+
+    #[test]
+    fn test_history_drops_oldest() {
+        let mut history = History::default();
+        for index in 0..MAX_ENTRIES + 5 {
+            history.push(Lookup::Search(index.to_string()));
+        }
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(history.current(), Some(&Lookup::Search((MAX_ENTRIES + 4).to_string())));
+        while history.can_go_back() {
+            history.back();
+        }
+        assert_eq!(history.current(), Some(&Lookup::Search("5".to_string())));
+    }
+}
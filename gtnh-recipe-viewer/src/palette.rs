@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+use gtnh_recipe_lib::types::json::JsonFormat;
+use crate::i18n::{tr, tr_args};
+use crate::shortcuts::{Action, Shortcuts};
+
+/// Maximum number of entries listed in the palette
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteEntry {
+    Action(Action),
+    /// Show the recipes of a machine
+    Machine(String),
+    /// Show the recipes producing an item or fluid
+    Ingredient(String),
+}
+
+/// Machine and ingredient names of the loaded dump, sorted
+#[derive(Default)]
+struct Names {
+    /// Names with their lowercase form, to not lowercase every name on each keystroke
+    machines: Vec<(String, String)>,
+    ingredients: Vec<(String, String)>,
+}
+
+fn with_lowercase(names: impl IntoIterator<Item = String>) -> Vec<(String, String)> {
+    names.into_iter().map(|name| {
+        let lowercase = name.to_lowercase();
+        (name, lowercase)
+    }).collect()
+}
+
+impl Names {
+    fn new(recipes: &JsonFormat) -> Self {
+        let mut machines = BTreeSet::new();
+        let mut ingredients = BTreeSet::new();
+        for machine in recipes.sources.iter().flat_map(|source| &source.machines) {
+            machines.insert(machine.name.clone());
+            for recipe in &machine.recipes {
+                ingredients.extend(recipe.item_inputs.iter().chain(&recipe.item_outputs).map(|item| item.get_name()));
+                ingredients.extend(recipe.fluid_inputs.iter().chain(&recipe.fluid_outputs).map(|fluid| fluid.get_name()));
+            }
+        }
+        Names {
+            machines: with_lowercase(machines),
+            ingredients: with_lowercase(ingredients),
+        }
+    }
+}
+
+/// Entries containing all words of `query`, actions first, then machines and ingredients.
+fn matching_entries(ctx: &egui::Context, query: &str, names: &Names) -> Vec<PaletteEntry> {
+    let words = query.to_lowercase().split_whitespace().map(str::to_string).collect::<Vec<String>>();
+    let matches = |lowercase: &str| words.iter().all(|word| lowercase.contains(word));
+    Action::ALL
+        .into_iter()
+        .filter(|action| *action != Action::CommandPalette && matches(&action.label(ctx).to_lowercase()))
+        .map(PaletteEntry::Action)
+        .chain(names.machines.iter().filter(|(_, lowercase)| matches(lowercase)).map(|(name, _)| PaletteEntry::Machine(name.clone())))
+        .chain(
+            // Listing every ingredient of the dump isn't useful without a query
+            names.ingredients.iter()
+                .filter(|(_, lowercase)| !words.is_empty() && matches(lowercase))
+                .map(|(name, _)| PaletteEntry::Ingredient(name.clone())),
+        )
+        .take(MAX_ENTRIES)
+        .collect()
+}
+
+/// Ctrl+K popup to run viewer actions and jump to machines and items by name
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+    names: Option<Names>,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Forgets the names of the previous dump.
+    pub fn invalidate(&mut self) {
+        self.names = None;
+    }
+
+    /// Shows the palette, returns the entry the user picked.
+    pub fn ui(&mut self, ctx: &egui::Context, recipes: Option<&JsonFormat>, shortcuts: &Shortcuts) -> Option<PaletteEntry> {
+        if !self.open {
+            return None;
+        }
+        if self.names.is_none() {
+            self.names = recipes.map(Names::new);
+        }
+        let empty = Names::default();
+        let entries = matching_entries(ctx, &self.query, self.names.as_ref().unwrap_or(&empty));
+
+        let (up, down, enter, escape) = ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if down {
+            self.selected = (self.selected + 1).min(entries.len().saturating_sub(1));
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        let mut picked = if enter { entries.get(self.selected).cloned() } else { None };
+
+        egui::Window::new(tr(ctx, "Command palette"))
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .fixed_size(egui::vec2(400.0, 0.0))
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(tr(ui.ctx(), "Type an action, machine or item"))
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.selected = 0;
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    if entries.is_empty() {
+                        ui.weak(tr(ui.ctx(), "Nothing found"));
+                    }
+                    for (index, entry) in entries.iter().enumerate() {
+                        let text = match entry {
+                            PaletteEntry::Action(action) => {
+                                let shortcut = shortcuts.format(ctx, *action);
+                                if shortcut.is_empty() {
+                                    action.label(ctx).to_string()
+                                } else {
+                                    format!("{}  ({})", action.label(ctx), shortcut)
+                                }
+                            }
+                            PaletteEntry::Machine(name) => tr_args(ctx, "Machine: {}", &[name]),
+                            PaletteEntry::Ingredient(name) => tr_args(ctx, "Recipes for {}", &[name]),
+                        };
+                        let response = ui.selectable_label(index == self.selected, text);
+                        if index == self.selected && (up || down) {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            picked = Some(entry.clone());
+                        }
+                    }
+                });
+            });
+
+        if picked.is_some() || escape {
+            self.open = false;
+        }
+        picked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_entries() {
+        let names = Names {
+            machines: with_lowercase(["Electrolyzer".to_string(), "Mixer".to_string()]),
+            ingredients: with_lowercase(["Empty Cell".to_string(), "Redstone".to_string()]),
+        };
+        let ctx = egui::Context::default();
+        let entries = matching_entries(&ctx, "", &names);
+        assert!(entries.contains(&PaletteEntry::Machine("Mixer".to_string())));
+        assert!(!entries.iter().any(|entry| matches!(entry, PaletteEntry::Ingredient(_))));
+        assert!(!entries.contains(&PaletteEntry::Action(Action::CommandPalette)));
+
+        assert_eq!(matching_entries(&ctx, "red", &names), [PaletteEntry::Ingredient("Redstone".to_string())]);
+        assert_eq!(
+            matching_entries(&ctx, "toggle plan", &names),
+            [PaletteEntry::Action(Action::TogglePlanner)]
+        );
+        assert_eq!(
+            matching_entries(&ctx, "TAB", &names),
+            [PaletteEntry::Action(Action::NewTab), PaletteEntry::Action(Action::CloseTab)]
+        );
+        assert_eq!(
+            matching_entries(&ctx, "el", &names),
+            [
+                PaletteEntry::Machine("Electrolyzer".to_string()),
+                PaletteEntry::Ingredient("Empty Cell".to_string()),
+            ]
+        );
+    }
+}
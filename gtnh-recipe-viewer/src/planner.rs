@@ -0,0 +1,202 @@
+use gtnh_recipe_lib::export::card::Card;
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::production_chain::{ChainSolution, ChainStep, IngredientRate, ProductionChain};
+use gtnh_recipe_lib::types::voltage_tier::VoltageTier;
+use crate::i18n::{tr, tr_args};
+use crate::icons::Icons;
+
+/// Production chain the user is planning, solved live while editing
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct Planner {
+    pub open: bool,
+    chain: ProductionChain,
+    target: String,
+    /// Requested amount of `target` per second
+    rate: f64,
+
+    #[serde(skip)]
+    solution: Option<ChainSolution>,
+}
+
+impl Default for Planner {
+    fn default() -> Self {
+        Self {
+            open: false,
+            chain: Default::default(),
+            target: String::new(),
+            rate: 1.0,
+            solution: None,
+        }
+    }
+}
+
+impl Planner {
+    pub fn add_recipe(&mut self, machine_name: &str, recipe: &GregtechRecipe) {
+        let step = ChainStep::new(machine_name, recipe);
+        if self.target.is_empty() {
+            self.target = step.main_output().unwrap_or_default();
+        }
+        self.chain.steps.push(step);
+        self.open = true;
+        self.solution = None;
+    }
+
+    /// Summary of the solved chain for sharing, `None` without steps.
+    pub fn card(&self) -> Option<Card> {
+        if self.chain.steps.is_empty() {
+            return None;
+        }
+        let solution = self.solution.clone().unwrap_or_else(|| self.chain.solve(&self.target, self.rate));
+        Some(Card::from_solution(&solution, &self.target, self.rate))
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, icons: &Icons) {
+        if self.chain.steps.is_empty() {
+            ui.label(tr(ui.ctx(), "Add recipes from the search results (right click a row) to plan a production chain."));
+            return;
+        }
+
+        let mut changed = false;
+        egui::Grid::new("planner_target").num_columns(2).show(ui, |ui| {
+            ui.label(tr(ui.ctx(), "Target:"));
+            egui::ComboBox::from_id_source("planner_target_combo")
+                .selected_text(&self.target)
+                .show_ui(ui, |ui| {
+                    for output in self.outputs() {
+                        changed |= ui.selectable_value(&mut self.target, output.clone(), output).changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr(ui.ctx(), "Rate:"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.rate).speed(0.1).range(0.0..=f64::MAX).suffix(" /s"))
+                .changed();
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.strong(tr(ui.ctx(), "Steps"));
+        changed |= self.steps_ui(ui);
+
+        if changed || self.solution.is_none() {
+            self.solution = Some(self.chain.solve(&self.target, self.rate));
+        }
+        if let Some(solution) = &self.solution {
+            ui.separator();
+            solution_ui(ui, icons, solution, &self.target);
+        }
+
+        ui.separator();
+        if ui.button(tr(ui.ctx(), "Clear")).clicked() {
+            *self = Planner { open: true, ..Default::default() };
+        }
+    }
+
+    /// Editable list of steps, returns whether the chain was changed.
+    fn steps_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        let mut removed = None;
+        egui::Grid::new("planner_steps").striped(true).num_columns(4).show(ui, |ui| {
+            for (index, step) in self.chain.steps.iter_mut().enumerate() {
+                ui.label(&step.machine_name);
+
+                let minimum_tier = VoltageTier::for_eut(step.recipe.eut);
+                let mut tier = step.tier();
+                egui::ComboBox::from_id_source(("planner_tier", index))
+                    .width(60.0)
+                    .selected_text(tier.to_string())
+                    .show_ui(ui, |ui| {
+                        for option in VoltageTier::ALL.into_iter().filter(|option| *option >= minimum_tier) {
+                            ui.selectable_value(&mut tier, option, option.to_string());
+                        }
+                    });
+                if tier != step.tier() {
+                    step.tier = Some(tier);
+                    changed = true;
+                }
+
+                let mut main_output = step.main_output();
+                egui::ComboBox::from_id_source(("planner_main_output", index))
+                    .selected_text(main_output.as_deref().unwrap_or_default())
+                    .show_ui(ui, |ui| {
+                        for output in recipe_outputs(&step.recipe) {
+                            ui.selectable_value(&mut main_output, Some(output.clone()), output);
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr(ui.ctx(), "Output this step runs for, other outputs are byproducts"));
+                if main_output != step.main_output() {
+                    step.main_output = main_output;
+                    changed = true;
+                }
+
+                if ui.small_button("🗑").on_hover_text(tr(ui.ctx(), "Remove step")).clicked() {
+                    removed = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = removed {
+            self.chain.steps.remove(index);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Outputs of all steps, each name once.
+    fn outputs(&self) -> Vec<String> {
+        let mut outputs = vec![];
+        for output in self.chain.steps.iter().flat_map(|step| recipe_outputs(&step.recipe)) {
+            if !outputs.contains(&output) {
+                outputs.push(output);
+            }
+        }
+        outputs
+    }
+}
+
+fn recipe_outputs(recipe: &GregtechRecipe) -> Vec<String> {
+    recipe.item_outputs.iter().map(|item| item.get_name())
+        .chain(recipe.fluid_outputs.iter().map(|fluid| fluid.get_name()))
+        .collect()
+}
+
+fn solution_ui(ui: &mut egui::Ui, icons: &Icons, solution: &ChainSolution, target: &str) {
+    if !solution.converged {
+        ui.colored_label(egui::Color32::RED, tr(ui.ctx(), "The chain contains a loop that could not be balanced!"));
+    }
+
+    egui::Grid::new("planner_solution").striped(true).num_columns(4).show(ui, |ui| {
+        ui.strong(tr(ui.ctx(), "Machine"));
+        ui.strong(tr(ui.ctx(), "Count"));
+        ui.strong("EU/t");
+        ui.strong(tr(ui.ctx(), "Duration"));
+        ui.end_row();
+        for step in &solution.steps {
+            ui.label(format!("{} ({})", step.machine_name, step.tier));
+            ui.label(format!("{:.2}", step.machines));
+            ui.label(step.eut.to_string());
+            ui.label(format!("{:.2}s", step.duration as f64 / 20.0));
+            ui.end_row();
+        }
+    });
+    ui.label(tr_args(ui.ctx(), "Power: {} EU/t", &[&format!("{:.1}", solution.power)]));
+
+    rates_ui(ui, icons, "Inputs", &solution.inputs);
+    rates_ui(ui, icons, "Byproducts", &solution.byproducts(target));
+}
+
+fn rates_ui(ui: &mut egui::Ui, icons: &Icons, heading: &'static str, rates: &[IngredientRate]) {
+    ui.strong(tr(ui.ctx(), heading));
+    if rates.is_empty() {
+        ui.label(tr(ui.ctx(), "None"));
+    }
+    for rate in rates {
+        ui.horizontal(|ui| {
+            icons.icon_ui(ui, [Some(&rate.name), None]);
+            ui.label(format!("{:.2}/s {}", rate.rate, rate.name));
+        });
+    }
+}
@@ -0,0 +1,78 @@
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use crate::canvas::Canvas;
+use crate::comparison::Comparison;
+use crate::navigation::History;
+use crate::planner::Planner;
+use crate::tabs::SearchTab;
+
+/// File extension of exported project files
+pub const PROJECT_EXTENSION: &str = "gtnhproject.json";
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+pub struct SelectedRecipe {
+    pub machine_name: String,
+    pub recipe: GregtechRecipe,
+    /// Whether the details window of the recipe is open
+    pub window_open: bool,
+}
+
+/// Everything the user worked on, saved under a name or to a project file
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Project {
+    pub name: String,
+    /// Search and history of the active tab, also read from projects saved before tabs existed
+    pub search: String,
+    pub history: History,
+    pub tabs: Vec<SearchTab>,
+    pub active_tab: usize,
+    pub selection: Vec<SelectedRecipe>,
+    pub planner: Planner,
+    pub canvas: Canvas,
+    pub comparison: Comparison,
+}
+
+impl Project {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(bytes: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(bytes)
+    }
+
+    pub fn file_name(&self) -> String {
+        let name = if self.name.is_empty() { "project" } else { &self.name };
+        format!("{}.{}", name, PROJECT_EXTENSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_json_roundtrip() {
+        let project = Project {
+            name: "Helium".to_string(),
+            search: "helium".to_string(),
+            selection: vec![SelectedRecipe {
+                machine_name: "Electrolyzer".to_string(),
+                recipe: RecipeBuilder::new(30, 100).build(),
+                window_open: true,
+            }],
+            ..Default::default()
+        };
+        let loaded = Project::from_json(project.to_json().unwrap().as_bytes()).unwrap();
+        assert_eq!(loaded.name, project.name);
+        assert_eq!(loaded.search, project.search);
+        assert_eq!(loaded.selection, project.selection);
+        assert_eq!(project.file_name(), "Helium.gtnhproject.json");
+
+        // Files of older versions may lack fields
+        let minimal = Project::from_json(br#"{"name": "empty"}"#).unwrap();
+        assert!(minimal.selection.is_empty());
+        assert!(minimal.tabs.is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeSet;
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use gtnh_recipe_lib::types::ingredients::NameDisplay;
+use crate::i18n::{tr, tr_args, IngredientText};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum SortColumn {
+    Machine,
+    Eut,
+    Duration,
+    Inputs,
+    Outputs,
+}
+
+/// Text filters of the results table, one per column
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ColumnFilters {
+    pub machine: String,
+    /// Comparison like `>= 480`, or a plain number for equality
+    pub eut: String,
+    /// Comparison in seconds like `< 10`, or a plain number for equality
+    pub duration: String,
+    pub item_inputs: String,
+    pub fluid_inputs: String,
+    pub item_outputs: String,
+    pub fluid_outputs: String,
+}
+
+/// Sorting and filtering of the search results
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ResultsTable {
+    /// Sorted column and whether it is sorted ascending
+    pub sort: Option<(SortColumn, bool)>,
+    pub filters: ColumnFilters,
+    /// Machines to show, all if empty
+    pub machines: BTreeSet<String>,
+
+    /// Indices of the visible search results, in display order
+    #[serde(skip)]
+    rows: Option<Vec<usize>>,
+    /// Visible row chosen with the keyboard
+    #[serde(skip)]
+    pub cursor: Option<usize>,
+    /// Whether the table should scroll to the cursor in the next frame
+    #[serde(skip)]
+    pub scroll_to_cursor: bool,
+}
+
+pub fn ingredient_list<T: IngredientText>(ingredients: &[T], names: NameDisplay) -> String {
+    ingredients
+        .iter()
+        .map(|ingredient| ingredient.text(names))
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+/// Whether `value` satisfies a filter like `>= 120`, an empty filter matches everything.
+fn matches_number(filter: &str, value: f64) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() {
+        return true;
+    }
+    let (operator, number) = ["<=", ">=", "<", ">", "="]
+        .iter()
+        .find_map(|operator| filter.strip_prefix(operator).map(|number| (*operator, number)))
+        .unwrap_or(("=", filter));
+    match number.trim().parse::<f64>() {
+        Ok(number) => match operator {
+            "<=" => value <= number,
+            ">=" => value >= number,
+            "<" => value < number,
+            ">" => value > number,
+            _ => value == number,
+        },
+        // Ignore filters that are still being typed
+        Err(_) => true,
+    }
+}
+
+fn matches_text(filter: &str, text: &str) -> bool {
+    text.to_lowercase().contains(&filter.trim().to_lowercase())
+}
+
+impl ResultsTable {
+    /// Forgets the visible rows, e.g. after the search results changed.
+    pub fn invalidate(&mut self) {
+        self.rows = None;
+        self.cursor = None;
+    }
+
+    /// Moves the keyboard cursor by `delta` rows, staying within the visible rows.
+    pub fn move_cursor(&mut self, delta: isize, results: &[(String, GregtechRecipe)]) {
+        let count = self.rows(results).len();
+        if count == 0 {
+            return;
+        }
+        self.cursor = Some(match self.cursor {
+            Some(cursor) => cursor.saturating_add_signed(delta).min(count - 1),
+            None if delta < 0 => count - 1,
+            None => 0,
+        });
+        self.scroll_to_cursor = true;
+    }
+
+    /// Index into the search results of the row under the keyboard cursor.
+    pub fn cursor_row(&mut self, results: &[(String, GregtechRecipe)]) -> Option<usize> {
+        let cursor = self.cursor?;
+        self.rows(results).get(cursor).copied()
+    }
+
+    pub fn toggle_sort(&mut self, column: SortColumn) {
+        self.sort = match self.sort {
+            Some((sorted, true)) if sorted == column => Some((column, false)),
+            Some((sorted, false)) if sorted == column => None,
+            _ => Some((column, true)),
+        };
+        self.invalidate();
+    }
+
+    /// Ingredient filters match the localized or unlocalized names, whichever names are shown.
+    fn matches(&self, machine_name: &str, recipe: &GregtechRecipe) -> bool {
+        let filters = &self.filters;
+        (self.machines.is_empty() || self.machines.contains(machine_name))
+            && matches_text(&filters.machine, machine_name)
+            && matches_number(&filters.eut, recipe.eut as f64)
+            && matches_number(&filters.duration, recipe.duration as f64 / 20.0)
+            && matches_text(&filters.item_inputs, &ingredient_list(&recipe.item_inputs, NameDisplay::Both))
+            && matches_text(&filters.fluid_inputs, &ingredient_list(&recipe.fluid_inputs, NameDisplay::Both))
+            && matches_text(&filters.item_outputs, &ingredient_list(&recipe.item_outputs, NameDisplay::Both))
+            && matches_text(&filters.fluid_outputs, &ingredient_list(&recipe.fluid_outputs, NameDisplay::Both))
+    }
+
+    /// Indices into `results` of the rows to show, filtered and sorted.
+    pub fn rows(&mut self, results: &[(String, GregtechRecipe)]) -> &[usize] {
+        if self.rows.is_none() {
+            let mut rows = (0..results.len())
+                .filter(|index| self.matches(&results[*index].0, &results[*index].1))
+                .collect::<Vec<usize>>();
+            if let Some((column, ascending)) = self.sort {
+                // Stable sort, so equal rows keep the relevance order of the search
+                rows.sort_by(|a, b| {
+                    let ((machine_a, a), (machine_b, b)) = (&results[*a], &results[*b]);
+                    let ordering = match column {
+                        SortColumn::Machine => machine_a.cmp(machine_b),
+                        SortColumn::Eut => a.eut.cmp(&b.eut),
+                        SortColumn::Duration => a.duration.cmp(&b.duration),
+                        SortColumn::Inputs => (a.item_inputs.len() + a.fluid_inputs.len())
+                            .cmp(&(b.item_inputs.len() + b.fluid_inputs.len())),
+                        SortColumn::Outputs => (a.item_outputs.len() + a.fluid_outputs.len())
+                            .cmp(&(b.item_outputs.len() + b.fluid_outputs.len())),
+                    };
+                    if ascending { ordering } else { ordering.reverse() }
+                });
+            }
+            self.rows = Some(rows);
+        }
+        self.rows.as_deref().unwrap_or_default()
+    }
+
+    /// Column header with a sort button and a filter text box below it, `color` highlights the title.
+    pub fn header_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        title: &str,
+        color: Option<egui::Color32>,
+        column: Option<SortColumn>,
+        filter: fn(&mut ColumnFilters) -> &mut String,
+    ) {
+        let color = color.unwrap_or_else(|| ui.visuals().strong_text_color());
+        ui.vertical(|ui| {
+            match column {
+                Some(column) => {
+                    let arrow = match self.sort {
+                        Some((sorted, true)) if sorted == column => " ⏶",
+                        Some((sorted, false)) if sorted == column => " ⏷",
+                        _ => "",
+                    };
+                    if ui.add(egui::Button::new(egui::RichText::new(format!("{}{}", title, arrow)).strong().color(color)).frame(false))
+                        .on_hover_text(tr(ui.ctx(), "Sort"))
+                        .clicked()
+                    {
+                        self.toggle_sort(column);
+                    }
+                }
+                None => {
+                    ui.label(egui::RichText::new(title).strong().color(color));
+                }
+            }
+            if ui.add(egui::TextEdit::singleline(filter(&mut self.filters)).hint_text(tr(ui.ctx(), "Filter"))).changed() {
+                self.invalidate();
+            }
+        });
+    }
+
+    /// Checkbox menu to limit the results to some machines.
+    pub fn machine_filter_ui(&mut self, ui: &mut egui::Ui, results: &[(String, GregtechRecipe)]) {
+        let machines = results.iter().map(|(machine_name, _)| machine_name.as_str()).collect::<BTreeSet<&str>>();
+        let title = if self.machines.is_empty() {
+            tr(ui.ctx(), "Machines: all").to_string()
+        } else {
+            tr_args(ui.ctx(), "Machines: {}", &[&self.machines.len()])
+        };
+        ui.menu_button(title, |ui| {
+            if ui.button(tr(ui.ctx(), "Show all")).clicked() {
+                self.machines.clear();
+                self.invalidate();
+            }
+            ui.separator();
+            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for machine in machines {
+                    let mut checked = self.machines.contains(machine);
+                    if ui.checkbox(&mut checked, machine).changed() {
+                        if checked {
+                            self.machines.insert(machine.to_string());
+                        } else {
+                            self.machines.remove(machine);
+                        }
+                        self.invalidate();
+                    }
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gtnh_recipe_lib::test_util::RecipeBuilder;
+
+    #[test]
+    fn test_number_filter() {
+        assert!(matches_number("", 5.0));
+        assert!(matches_number(">= 5", 5.0));
+        assert!(!matches_number("<5", 5.0));
+        assert!(matches_number("5", 5.0));
+        assert!(matches_number(">", 1.0));
+    }
+
+    #[test]
+    fn test_rows() {
+        let results = vec![
+            ("Mixer".to_string(), RecipeBuilder::new(30, 100).fluid_inputs(&[("Water", 1)]).build()),
+            ("Electrolyzer".to_string(), RecipeBuilder::new(120, 40).fluid_inputs(&[("Water", 2)]).build()),
+            ("Mixer".to_string(), RecipeBuilder::new(8, 200).fluid_inputs(&[("Water", 3)]).build()),
+        ];
+        let mut table = ResultsTable::default();
+        assert_eq!(table.rows(&results), [0, 1, 2]);
+
+        table.toggle_sort(SortColumn::Eut);
+        assert_eq!(table.rows(&results), [2, 0, 1]);
+        table.toggle_sort(SortColumn::Eut);
+        assert_eq!(table.rows(&results), [1, 0, 2]);
+
+        table.filters.duration = "> 2".to_string();
+        table.machines.insert("Mixer".to_string());
+        table.invalidate();
+        assert_eq!(table.rows(&results), [0, 2]);
+
+        table.filters.fluid_inputs = "3l water".to_string();
+        table.invalidate();
+        assert_eq!(table.rows(&results), [2]);
+
+        table.move_cursor(1, &results);
+        assert_eq!(table.cursor_row(&results), Some(2));
+        table.move_cursor(-1, &results);
+        assert_eq!(table.cursor_row(&results), Some(2));
+    }
+}
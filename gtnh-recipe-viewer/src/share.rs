@@ -0,0 +1,163 @@
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use gtnh_recipe_lib::export::card::{cards_to_string, Card, CardFormat, CardLineStyle};
+use image::{Rgba, RgbaImage};
+use log::error;
+use crate::i18n::{tr, tr_args};
+
+/// PNG cards are rendered at twice the SVG size, so they stay sharp on high DPI screens
+const FONT_SIZE: f32 = 28.0;
+const LINE_HEIGHT: f32 = FONT_SIZE * 1.4;
+const PADDING: f32 = 24.0;
+const CARD_GAP: f32 = 20.0;
+
+const BACKGROUND: Rgba<u8> = Rgba([0x1b, 0x1b, 0x1b, 0xff]);
+const BORDER: Rgba<u8> = Rgba([0x5a, 0x5a, 0x5a, 0xff]);
+
+fn line_color(style: CardLineStyle) -> [u8; 3] {
+    match style {
+        CardLineStyle::Title => [0xff, 0xff, 0xff],
+        CardLineStyle::Subtitle => [0x9c, 0xcf, 0xff],
+        CardLineStyle::Heading => [0xff, 0xd4, 0x79],
+        CardLineStyle::Entry => [0xdd, 0xdd, 0xdd],
+    }
+}
+
+fn line_scale(style: CardLineStyle) -> PxScale {
+    PxScale::from(if style == CardLineStyle::Title { FONT_SIZE * 1.25 } else { FONT_SIZE })
+}
+
+/// The monospace font egui ships with, so rendering doesn't depend on installed fonts.
+fn card_font() -> Result<FontArc, String> {
+    let data = egui::FontDefinitions::default()
+        .font_data
+        .remove("Hack")
+        .ok_or_else(|| "monospace font missing".to_string())?;
+    FontArc::try_from_vec(data.font.into_owned()).map_err(|err| err.to_string())
+}
+
+fn text_width(font: &FontArc, scale: PxScale, text: &str) -> f32 {
+    let font = font.as_scaled(scale);
+    text.chars().map(|c| font.h_advance(font.glyph_id(c))).sum()
+}
+
+fn indent(font: &FontArc, style: CardLineStyle) -> f32 {
+    if style == CardLineStyle::Entry { 2.0 * text_width(font, line_scale(style), " ") } else { 0.0 }
+}
+
+fn draw_text(image: &mut RgbaImage, font: &FontArc, scale: PxScale, position: (f32, f32), color: [u8; 3], text: &str) {
+    let scaled = font.as_scaled(scale);
+    let mut x = position.0;
+    for c in text.chars() {
+        let glyph_id = scaled.glyph_id(c);
+        let glyph = glyph_id.with_scale_and_position(scale, ab_glyph::point(x, position.1));
+        x += scaled.h_advance(glyph_id);
+        let Some(outline) = font.outline_glyph(glyph) else { continue };
+        let bounds = outline.px_bounds();
+        outline.draw(|glyph_x, glyph_y, coverage| {
+            let (pixel_x, pixel_y) = (bounds.min.x as i64 + glyph_x as i64, bounds.min.y as i64 + glyph_y as i64);
+            if pixel_x < 0 || pixel_y < 0 || pixel_x >= image.width() as i64 || pixel_y >= image.height() as i64 {
+                return;
+            }
+            let pixel = image.get_pixel_mut(pixel_x as u32, pixel_y as u32);
+            for (channel, value) in pixel.0.iter_mut().zip(color) {
+                *channel = (*channel as f32 * (1.0 - coverage) + value as f32 * coverage).round() as u8;
+            }
+        });
+    }
+}
+
+/// Renders cards like the SVG export into a PNG, on the CPU.
+pub fn cards_to_png(cards: &[Card]) -> Result<Vec<u8>, String> {
+    let font = card_font()?;
+    let width = cards
+        .iter()
+        .flat_map(|card| card.lines())
+        .map(|(style, line)| indent(&font, style) + text_width(&font, line_scale(style), &line))
+        .fold(0.0, f32::max)
+        + 2.0 * PADDING;
+    let card_height = |card: &Card| card.lines().len() as f32 * LINE_HEIGHT + 2.0 * PADDING;
+    let height = cards.iter().map(|card| card_height(card) + CARD_GAP).sum::<f32>().max(CARD_GAP) - CARD_GAP;
+
+    let mut image = RgbaImage::new(width.ceil().max(1.0) as u32, height.ceil().max(1.0) as u32);
+    let mut top = 0.0;
+    for card in cards {
+        let (card_top, card_bottom) = (top as u32, ((top + card_height(card)).ceil() as u32).min(image.height()));
+        for y in card_top..card_bottom {
+            for x in 0..image.width() {
+                let border = x == 0 || x == image.width() - 1 || y == card_top || y == card_bottom - 1;
+                image.put_pixel(x, y, if border { BORDER } else { BACKGROUND });
+            }
+        }
+        let mut baseline = top + PADDING + LINE_HEIGHT * 0.8;
+        for (style, line) in card.lines() {
+            let position = (PADDING + indent(&font, style), baseline);
+            draw_text(&mut image, &font, line_scale(style), position, line_color(style), &line);
+            baseline += LINE_HEIGHT;
+        }
+        top += card_height(card) + CARD_GAP;
+    }
+
+    let mut png = std::io::Cursor::new(vec![]);
+    image.write_to(&mut png, image::ImageFormat::Png).map_err(|err| err.to_string())?;
+    Ok(png.into_inner())
+}
+
+fn save_file(title: &'static str, file_name: String, contents: Vec<u8>) {
+    let task = rfd::AsyncFileDialog::new().set_title(title).set_file_name(file_name).save_file();
+    crate::app::execute(async move {
+        if let Some(file) = task.await {
+            if let Err(err) = file.write(&contents).await {
+                error!("unable to write exported cards: {}", err);
+            }
+        }
+    });
+}
+
+/// Copy and save buttons for sharing `cards`, `name` is the default file name.
+pub fn share_menu_ui(ui: &mut egui::Ui, cards: &[Card], name: &str) {
+    for (format, label) in [(CardFormat::Markdown, "Markdown"), (CardFormat::Text, "text")] {
+        if ui.button(tr_args(ui.ctx(), "Copy as {}", &[&tr(ui.ctx(), label)])).clicked() {
+            ui.ctx().copy_text(cards_to_string(cards, format));
+            ui.close_menu();
+        }
+    }
+    ui.separator();
+    for (format, label) in [(CardFormat::Markdown, "Markdown"), (CardFormat::Text, "text"), (CardFormat::Svg, "SVG image")] {
+        if ui.button(tr_args(ui.ctx(), "Save as {}…", &[&tr(ui.ctx(), label)])).clicked() {
+            let contents = cards_to_string(cards, format).into_bytes();
+            save_file(tr(ui.ctx(), "Export cards"), format!("{}.{}", name, format.extension()), contents);
+            ui.close_menu();
+        }
+    }
+    if ui.button(tr(ui.ctx(), "Save as PNG image…")).clicked() {
+        match cards_to_png(cards) {
+            Ok(png) => save_file(tr(ui.ctx(), "Export cards"), format!("{}.png", name), png),
+            Err(err) => error!("unable to render cards: {}", err),
+        }
+        ui.close_menu();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png() {
+        let card = Card {
+            title: "Mixer".to_string(),
+            subtitle: "30 EU/t, 5s".to_string(),
+            sections: vec![("Inputs".to_string(), vec!["1000l Water".to_string()]), ("Outputs".to_string(), vec![])],
+        };
+        let png = cards_to_png(&[card.clone(), card]).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgba8();
+        // Four lines per card, two cards and the gap between them
+        assert_eq!(image.height(), (2.0 * (4.0 * LINE_HEIGHT + 2.0 * PADDING) + CARD_GAP).ceil() as u32);
+        assert!(image.width() as f32 > 2.0 * PADDING);
+        // Some text was drawn in the title color
+        assert!(image.pixels().any(|pixel| pixel.0 == [0xff, 0xff, 0xff, 0xff]));
+        // The gap between the cards stays transparent
+        let gap = (4.0 * LINE_HEIGHT + 2.0 * PADDING + CARD_GAP / 2.0) as u32;
+        assert_eq!(image.get_pixel(0, gap).0[3], 0);
+    }
+}
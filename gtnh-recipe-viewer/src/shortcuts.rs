@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use egui::{Key, KeyboardShortcut, Modifiers};
+use crate::i18n::tr;
+
+/// Viewer actions that can be bound to keys and run from the command palette
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    CommandPalette,
+    FocusSearch,
+    NextRow,
+    PreviousRow,
+    OpenDetails,
+    /// Recipes producing the hovered ingredient or the first output of the current row (NEI "R")
+    LookupRecipes,
+    /// Recipes using the hovered ingredient or the first output of the current row (NEI "U")
+    LookupUses,
+    CloseWindows,
+    Back,
+    Forward,
+    NewTab,
+    CloseTab,
+    ToggleFavourites,
+    ToggleMachines,
+    TogglePlanner,
+    ToggleCanvas,
+    ToggleComparison,
+    ToggleMaterials,
+}
+
+impl Action {
+    pub const ALL: [Action; 18] = [
+        Action::CommandPalette,
+        Action::FocusSearch,
+        Action::NextRow,
+        Action::PreviousRow,
+        Action::OpenDetails,
+        Action::LookupRecipes,
+        Action::LookupUses,
+        Action::CloseWindows,
+        Action::Back,
+        Action::Forward,
+        Action::NewTab,
+        Action::CloseTab,
+        Action::ToggleFavourites,
+        Action::ToggleMachines,
+        Action::TogglePlanner,
+        Action::ToggleCanvas,
+        Action::ToggleComparison,
+        Action::ToggleMaterials,
+    ];
+
+    pub fn label(self, ctx: &egui::Context) -> &'static str {
+        tr(ctx, match self {
+            Action::CommandPalette => "Command palette",
+            Action::FocusSearch => "Focus search",
+            Action::NextRow => "Next result",
+            Action::PreviousRow => "Previous result",
+            Action::OpenDetails => "Open or close details of the result",
+            Action::LookupRecipes => "Show recipes (R)",
+            Action::LookupUses => "Show uses (U)",
+            Action::CloseWindows => "Close windows",
+            Action::Back => "Back",
+            Action::Forward => "Forward",
+            Action::NewTab => "New tab",
+            Action::CloseTab => "Close tab",
+            Action::ToggleFavourites => "Toggle favourites",
+            Action::ToggleMachines => "Toggle machine list",
+            Action::TogglePlanner => "Toggle planner",
+            Action::ToggleCanvas => "Toggle canvas",
+            Action::ToggleComparison => "Toggle comparison",
+            Action::ToggleMaterials => "Toggle bill of materials",
+        })
+    }
+
+    fn default_shortcut(self) -> Option<KeyboardShortcut> {
+        let shortcut = |modifiers, key| Some(KeyboardShortcut::new(modifiers, key));
+        match self {
+            Action::CommandPalette => shortcut(Modifiers::COMMAND, Key::K),
+            Action::FocusSearch => shortcut(Modifiers::COMMAND, Key::F),
+            Action::NextRow => shortcut(Modifiers::NONE, Key::ArrowDown),
+            Action::PreviousRow => shortcut(Modifiers::NONE, Key::ArrowUp),
+            Action::OpenDetails => shortcut(Modifiers::NONE, Key::Enter),
+            Action::LookupRecipes => shortcut(Modifiers::NONE, Key::R),
+            Action::LookupUses => shortcut(Modifiers::NONE, Key::U),
+            Action::CloseWindows => shortcut(Modifiers::NONE, Key::Escape),
+            Action::Back => shortcut(Modifiers::ALT, Key::ArrowLeft),
+            Action::Forward => shortcut(Modifiers::ALT, Key::ArrowRight),
+            Action::NewTab => shortcut(Modifiers::COMMAND, Key::T),
+            Action::CloseTab => shortcut(Modifiers::COMMAND, Key::W),
+            Action::ToggleFavourites
+            | Action::ToggleMachines
+            | Action::TogglePlanner
+            | Action::ToggleCanvas
+            | Action::ToggleComparison
+            | Action::ToggleMaterials => None,
+        }
+    }
+}
+
+/// User configurable key bindings
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct Shortcuts {
+    pub open: bool,
+    bindings: BTreeMap<Action, KeyboardShortcut>,
+
+    /// Action waiting for its new key in the settings window
+    #[serde(skip)]
+    capturing: Option<Action>,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            open: false,
+            bindings: Action::ALL
+                .into_iter()
+                .filter_map(|action| action.default_shortcut().map(|shortcut| (action, shortcut)))
+                .collect(),
+            capturing: None,
+        }
+    }
+}
+
+impl Shortcuts {
+    pub fn get(&self, action: Action) -> Option<KeyboardShortcut> {
+        self.bindings.get(&action).copied()
+    }
+
+    /// Binds `shortcut` to `action`, removing it from any other action.
+    pub fn set(&mut self, action: Action, shortcut: KeyboardShortcut) {
+        self.bindings.retain(|_, bound| *bound != shortcut);
+        self.bindings.insert(action, shortcut);
+    }
+
+    /// Shortcut text for menus and the palette, empty if unbound.
+    pub fn format(&self, ctx: &egui::Context, action: Action) -> String {
+        self.get(action).map(|shortcut| ctx.format_shortcut(&shortcut)).unwrap_or_default()
+    }
+
+    /// Actions whose shortcut was pressed this frame.
+    ///
+    /// Shortcuts without modifiers are ignored while typing, so they don't swallow text input.
+    pub fn pressed(&self, ctx: &egui::Context) -> Vec<Action> {
+        if self.capturing.is_some() {
+            return vec![];
+        }
+        let typing = ctx.wants_keyboard_input();
+        self.bindings
+            .iter()
+            .filter(|(_, shortcut)| !typing || !shortcut.modifiers.is_none())
+            .filter(|(_, shortcut)| ctx.input_mut(|input| input.consume_shortcut(shortcut)))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context) {
+        if let Some(action) = self.capturing {
+            let pressed = ctx.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    egui::Event::Key { key, pressed: true, modifiers, .. } => Some(KeyboardShortcut::new(*modifiers, *key)),
+                    _ => None,
+                })
+            });
+            match pressed {
+                Some(shortcut) if shortcut == KeyboardShortcut::new(Modifiers::NONE, Key::Escape) => self.capturing = None,
+                Some(shortcut) => {
+                    self.set(action, shortcut);
+                    self.capturing = None;
+                }
+                None => {}
+            }
+        }
+
+        let mut open = self.open;
+        egui::Window::new(tr(ctx, "Keyboard shortcuts")).open(&mut open).show(ctx, |ui| {
+            egui::Grid::new("shortcuts_grid").striped(true).num_columns(3).show(ui, |ui| {
+                for action in Action::ALL {
+                    ui.label(action.label(ctx));
+                    if self.capturing == Some(action) {
+                        ui.label(tr(ui.ctx(), "Press a key… (Esc to cancel)"));
+                    } else if ui.button(self.format(ctx, action)).on_hover_text(tr(ui.ctx(), "Change")).clicked() {
+                        self.capturing = Some(action);
+                    }
+                    if ui.add_enabled(self.bindings.contains_key(&action), egui::Button::new("🗑").small())
+                        .on_hover_text(tr(ui.ctx(), "Remove shortcut"))
+                        .clicked()
+                    {
+                        self.bindings.remove(&action);
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            if ui.button(tr(ui.ctx(), "Reset to defaults")).clicked() {
+                *self = Shortcuts { open: true, ..Default::default() };
+            }
+        });
+        self.open = open;
+        if !self.open {
+            self.capturing = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebind() {
+        let mut shortcuts = Shortcuts::default();
+        let ctrl_k = KeyboardShortcut::new(Modifiers::COMMAND, Key::K);
+        assert_eq!(shortcuts.get(Action::CommandPalette), Some(ctrl_k));
+        assert_eq!(shortcuts.get(Action::TogglePlanner), None);
+
+        shortcuts.set(Action::TogglePlanner, ctrl_k);
+        assert_eq!(shortcuts.get(Action::TogglePlanner), Some(ctrl_k));
+        assert_eq!(shortcuts.get(Action::CommandPalette), None);
+
+        let json = serde_json::to_string(&shortcuts).unwrap();
+        let loaded: Shortcuts = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get(Action::TogglePlanner), Some(ctrl_k));
+    }
+}
@@ -0,0 +1,55 @@
+use gtnh_recipe_lib::types::gregtech_recipe::GregtechRecipe;
+use crate::i18n::tr;
+use crate::navigation::History;
+use crate::results_table::ResultsTable;
+
+/// Search with its own history and results table.
+///
+/// The app keeps the active tab in its own fields, the entry in its tab list is only updated
+/// when switching tabs or saving a project.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct SearchTab {
+    pub label: String,
+    pub history: History,
+    pub results_table: ResultsTable,
+
+    #[serde(skip)]
+    pub search_results: Vec<(String, GregtechRecipe)>,
+}
+
+impl SearchTab {
+    pub fn title(ctx: &egui::Context, history: &History) -> String {
+        history.current().map(|lookup| lookup.title(ctx)).unwrap_or_else(|| tr(ctx, "New tab").to_string())
+    }
+}
+
+pub enum TabAction {
+    Switch(usize),
+    Close(usize),
+    New,
+}
+
+/// Tab bar, `titles` in tab order.
+pub fn tab_bar_ui(ui: &mut egui::Ui, titles: &[String], active: usize) -> Option<TabAction> {
+    let mut action = None;
+    ui.horizontal_wrapped(|ui| {
+        for (index, title) in titles.iter().enumerate() {
+            let response = ui.selectable_label(index == active, title);
+            if response.clicked() {
+                action = Some(TabAction::Switch(index));
+            }
+            if response.middle_clicked() {
+                action = Some(TabAction::Close(index));
+            }
+            if titles.len() > 1 && ui.small_button("✖").on_hover_text(tr(ui.ctx(), "Close tab")).clicked() {
+                action = Some(TabAction::Close(index));
+            }
+            ui.separator();
+        }
+        if ui.button("➕").on_hover_text(tr(ui.ctx(), "New tab")).clicked() {
+            action = Some(TabAction::New);
+        }
+    });
+    action
+}
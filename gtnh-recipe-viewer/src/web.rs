@@ -0,0 +1,213 @@
+//! Browser specifics of the web build: the `?dump=` parameter and the IndexedDB dump cache.
+
+use gtnh_recipe_lib::load::BINARY_MAGIC;
+use gtnh_recipe_lib::types::recipe_id::fnv1a;
+use js_sys::{Date, Function, Promise, Uint8Array};
+use log::{error, info};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode, Response};
+
+const DATABASE: &str = "gtnh-recipe-viewer";
+const STORE: &str = "dumps";
+/// Cache key of the binary cache of the last loaded dump, the only dump kept
+const LAST_OPENED: &str = "last-opened";
+const LAST_OPENED_INFO: &str = "last-opened-info";
+
+/// Stored next to the last loaded dump, caches written by other viewer versions are ignored
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct SavedDumpInfo {
+    viewer_version: String,
+    /// Header of the binary cache, changes with its format
+    format: Vec<u8>,
+    /// Milliseconds since the epoch
+    saved_at: f64,
+    /// URL the dump was downloaded from and a hash of the download, to tell whether it changed since
+    #[serde(default)]
+    download: Option<(String, u64)>,
+}
+
+impl SavedDumpInfo {
+    pub fn current(download: Option<(String, u64)>) -> Self {
+        SavedDumpInfo {
+            viewer_version: env!("CARGO_PKG_VERSION").to_string(),
+            format: BINARY_MAGIC.to_vec(),
+            saved_at: Date::now(),
+            download,
+        }
+    }
+
+    fn is_compatible(&self) -> bool {
+        self.viewer_version == env!("CARGO_PKG_VERSION") && self.format == BINARY_MAGIC
+    }
+}
+
+fn js_error(value: JsValue) -> String {
+    value.as_string().unwrap_or_else(|| format!("{:?}", value))
+}
+
+/// Absolute URL of the `?dump=` query parameter, if it points to the origin of the app.
+///
+/// Relative paths are resolved against the page, so `?dump=recipes.json` loads a dump served
+/// next to the app.
+pub fn dump_url() -> Option<String> {
+    let location = web_sys::window()?.location();
+    let search = location.search().ok()?;
+    let dump = web_sys::UrlSearchParams::new_with_str(&search).ok()?.get("dump")?;
+    let url = web_sys::Url::new_with_base(&dump, &location.href().ok()?).ok()?;
+    if url.origin() != location.origin().ok()? {
+        error!("ignoring dump {} from another origin", dump);
+        return None;
+    }
+    Some(url.href())
+}
+
+pub async fn fetch(url: &str) -> Result<Vec<u8>, String> {
+    let window = web_sys::window().ok_or("no window")?;
+    let response: Response = JsFuture::from(window.fetch_with_str(url)).await.map_err(js_error)?.unchecked_into();
+    if !response.ok() {
+        return Err(format!("unable to download {}: HTTP {}", url, response.status()));
+    }
+    let buffer = JsFuture::from(response.array_buffer().map_err(js_error)?).await.map_err(js_error)?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
+/// Resolves with the result of an IndexedDB request.
+fn request_future(request: &IdbRequest) -> JsFuture {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let finished = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let _ = resolve.call1(&JsValue::NULL, &finished.result().unwrap_or(JsValue::UNDEFINED));
+        });
+        let on_error = Closure::once_into_js(move |event: JsValue| {
+            let _ = reject.call1(&JsValue::NULL, &event);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+}
+
+async fn open_database() -> Result<IdbDatabase, String> {
+    let factory = web_sys::window()
+        .ok_or("no window")?
+        .indexed_db()
+        .map_err(js_error)?
+        .ok_or("IndexedDB is not available")?;
+    let request = factory.open_with_u32(DATABASE, 1).map_err(js_error)?;
+    let upgraded = request.clone();
+    let on_upgrade = Closure::once_into_js(move || {
+        if let Ok(database) = upgraded.result() {
+            let _ = database.unchecked_into::<IdbDatabase>().create_object_store(STORE);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+    Ok(request_future(&request).await.map_err(js_error)?.unchecked_into())
+}
+
+pub async fn cache_get(key: &str) -> Result<Option<Vec<u8>>, String> {
+    let database = open_database().await?;
+    let store = database
+        .transaction_with_str(STORE)
+        .and_then(|transaction| transaction.object_store(STORE))
+        .map_err(js_error)?;
+    let value = request_future(&store.get(&JsValue::from_str(key)).map_err(js_error)?).await.map_err(js_error)?;
+    Ok((!value.is_undefined()).then(|| Uint8Array::new(&value).to_vec()))
+}
+
+pub async fn cache_put(key: &str, bytes: &[u8]) -> Result<(), String> {
+    let database = open_database().await?;
+    let store = database
+        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE))
+        .map_err(js_error)?;
+    let request = store
+        .put_with_key(&Uint8Array::from(bytes), &JsValue::from_str(key))
+        .map_err(js_error)?;
+    request_future(&request).await.map_err(js_error)?;
+    Ok(())
+}
+
+/// Removes every cached dump.
+pub async fn cache_clear() -> Result<(), String> {
+    let database = open_database().await?;
+    let store = database
+        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE))
+        .map_err(js_error)?;
+    request_future(&store.clear().map_err(js_error)?).await.map_err(js_error)?;
+    info!("cleared cached recipe dumps");
+    Ok(())
+}
+
+/// Bytes of a dump to load, and how to save them for the next visit.
+pub struct Dump {
+    pub bytes: Vec<u8>,
+    /// None if the bytes are the saved binary cache already
+    pub save_as: Option<SavedDumpInfo>,
+}
+
+/// Downloads the dump of `url`, falling back to the saved copy of an earlier visit when offline.
+///
+/// An unchanged download is loaded from the saved binary cache, which is faster than parsing it again.
+pub async fn fetch_dump(url: &str) -> Result<Dump, String> {
+    let saved_hash = saved_info().await.and_then(|info| info.download).and_then(|(saved_url, hash)| (saved_url == url).then_some(hash));
+    match fetch(url).await {
+        Ok(bytes) => {
+            let hash = fnv1a(&bytes);
+            if saved_hash == Some(hash) {
+                match cache_get(LAST_OPENED).await {
+                    Ok(Some(saved)) => return Ok(Dump { bytes: saved, save_as: None }),
+                    Ok(None) => {}
+                    Err(err) => error!("unable to read the saved dump: {}", err),
+                }
+            }
+            Ok(Dump { bytes, save_as: Some(SavedDumpInfo::current(Some((url.to_string(), hash)))) })
+        }
+        Err(err) if saved_hash.is_some() => {
+            error!("{}, loading the saved copy", err);
+            let saved = cache_get(LAST_OPENED).await?.ok_or(err)?;
+            Ok(Dump { bytes: saved, save_as: None })
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Keeps the binary cache of the loaded dump, so the next visit loads it without network access.
+pub async fn save_last_opened(bytes: Vec<u8>, info: SavedDumpInfo) -> Result<(), String> {
+    let info = serde_json::to_vec(&info).map_err(|err| err.to_string())?;
+    cache_put(LAST_OPENED, &bytes).await?;
+    cache_put(LAST_OPENED_INFO, &info).await
+}
+
+/// Info of the saved dump, if it was saved by this viewer version.
+async fn saved_info() -> Option<SavedDumpInfo> {
+    let info = match cache_get(LAST_OPENED_INFO).await {
+        Ok(info) => info?,
+        Err(err) => {
+            error!("unable to read the saved dump info: {}", err);
+            return None;
+        }
+    };
+    match serde_json::from_slice::<SavedDumpInfo>(&info) {
+        Ok(info) if info.is_compatible() => Some(info),
+        Ok(info) => {
+            info!("ignoring the recipe dump saved by viewer {}", info.viewer_version);
+            None
+        }
+        Err(err) => {
+            error!("unable to read the saved dump info: {}", err);
+            None
+        }
+    }
+}
+
+/// Binary cache of the last loaded dump, if one was saved by this viewer version.
+pub async fn load_last_opened() -> Result<Option<Dump>, String> {
+    let Some(info) = saved_info().await else {
+        return Ok(None);
+    };
+    info!("loading the recipe dump saved at {}", String::from(Date::new(&JsValue::from_f64(info.saved_at)).to_iso_string()));
+    Ok(cache_get(LAST_OPENED).await?.map(|bytes| Dump { bytes, save_as: None }))
+}